@@ -1,7 +1,21 @@
 pub mod lib {
+    pub mod analysis;
+    pub mod chord_scales;
+    pub mod chords;
     pub mod cli;
+    pub mod config;
+    pub mod edo;
     pub mod fret_board;
+    pub mod instruments;
     pub mod notes;
+    pub mod pitch_classes;
+    pub mod pitches;
+    pub mod progressions;
+    pub mod relations;
+    pub mod scala;
+    pub mod scale_search;
     pub mod scales;
+    pub mod spelling;
     pub mod tunings;
+    pub mod voicings;
 }
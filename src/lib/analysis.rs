@@ -0,0 +1,118 @@
+use super::chords::{get_diatonic_chord_steps, ChordType};
+use super::notes::{Note, NUM_NOTES};
+use super::pitch_classes::PitchClassSet;
+
+/// The interval structure of a scale. Degrees count from 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaleAnalysis {
+    /// The semitones from each note of the scale to the next, up to the octave.
+    pub step_sizes: Vec<usize>,
+    pub interval_vector: [usize; 6],
+    pub forte_number: Option<String>,
+    pub prime_form: Vec<usize>,
+    pub num_distinct_transpositions: usize,
+    pub num_tritones: usize,
+    /// The seventh chord on the root, or the triad if the scale has no seventh chord.
+    pub tonic_chord_steps: Option<Vec<usize>>,
+    /// The degrees that are not in the tonic chord and lie a semitone above one of
+    /// its notes.
+    pub avoid_notes: Vec<usize>,
+}
+
+pub fn analyze_scale(steps: &[usize]) -> ScaleAnalysis {
+    let pitch_classes = PitchClassSet::from_steps(Note::C, steps);
+    let step_sizes = (0..steps.len())
+        .map(|degree| {
+            let next_step = steps.get(degree + 1).copied().unwrap_or(NUM_NOTES);
+            next_step - steps[degree]
+        })
+        .collect();
+    let interval_vector = pitch_classes.interval_vector();
+
+    let tonic_chord_steps = get_diatonic_chord_steps(steps, 0, ChordType::Seventh)
+        .or_else(|| get_diatonic_chord_steps(steps, 0, ChordType::Triad));
+    let avoid_notes = tonic_chord_steps
+        .as_ref()
+        .map(|chord_steps| get_avoid_notes(steps, chord_steps, chord_steps))
+        .unwrap_or_default();
+
+    ScaleAnalysis {
+        step_sizes,
+        interval_vector,
+        forte_number: pitch_classes.forte_number(),
+        prime_form: pitch_classes.prime_form(),
+        num_distinct_transpositions: pitch_classes.num_distinct_transpositions(),
+        num_tritones: interval_vector[5],
+        tonic_chord_steps,
+        avoid_notes,
+    }
+}
+
+/// Returns the degrees of a scale (counting from 0) that are not among the steps of
+/// a chord and lie a semitone above one of its chord tones. The tensions of the
+/// chord are among its steps but not among its chord tones.
+pub fn get_avoid_notes(
+    steps: &[usize],
+    chord_steps: &[usize],
+    chord_tone_steps: &[usize],
+) -> Vec<usize> {
+    (0..steps.len())
+        .filter(|degree| {
+            let step = steps[*degree];
+            !chord_steps.contains(&step)
+                && chord_tone_steps.contains(&((step + NUM_NOTES - 1) % NUM_NOTES))
+        })
+        .collect()
+}
+
+/// Returns a step as H for a half step, W for a whole step, WH for a step and a half
+/// and WW for two whole steps, or its number of semitones otherwise.
+pub fn step_size_to_string(semitones: usize) -> String {
+    match semitones {
+        1 => String::from("H"),
+        2 => String::from("W"),
+        3 => String::from("WH"),
+        4 => String::from("WW"),
+        _ => semitones.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_major_scale() {
+        let analysis = analyze_scale(&[0, 2, 4, 5, 7, 9, 11]);
+        assert_eq!(
+            analysis
+                .step_sizes
+                .iter()
+                .map(|step_size| step_size_to_string(*step_size))
+                .collect::<Vec<String>>()
+                .join("-"),
+            "W-W-H-W-W-W-H"
+        );
+        assert_eq!(analysis.interval_vector, [2, 5, 4, 3, 6, 1]);
+        assert_eq!(analysis.forte_number, Some(String::from("7-35")));
+        assert_eq!(analysis.num_distinct_transpositions, 12);
+        assert_eq!(analysis.num_tritones, 1);
+        assert_eq!(analysis.tonic_chord_steps, Some(vec![0, 4, 7, 11]));
+        assert_eq!(analysis.avoid_notes, vec![3]);
+        assert_eq!(
+            get_avoid_notes(&[0, 2, 4, 5, 7, 9, 11], &[0, 4, 7, 11, 5], &[0, 4, 7, 11]),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_analyze_symmetric_scales() {
+        let analysis = analyze_scale(&[0, 2, 3, 5, 6, 8, 9, 11]);
+        assert_eq!(analysis.num_distinct_transpositions, 3);
+        assert_eq!(analysis.num_tritones, 4);
+        assert_eq!(analysis.forte_number, Some(String::from("8-28")));
+
+        let analysis = analyze_scale(&[0, 1, 3, 5, 7, 8, 10]);
+        assert_eq!(analysis.avoid_notes, vec![1, 5]);
+    }
+}
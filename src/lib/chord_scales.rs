@@ -0,0 +1,158 @@
+use super::analysis::get_avoid_notes;
+use super::chords::{parse_chord_symbol, Chord};
+use super::scales::ScaleChoice;
+use super::spelling::{Degree, SpelledNote};
+
+/// The chords on the root of a scale that are checked by `find_chords_for_scale`.
+pub const CHORD_SUFFIXES: [&str; 36] = [
+    "", "m", "dim", "aug", "sus2", "sus4", "6", "m6", "6/9", "m6/9", "maj7", "7", "m7", "m(maj7)",
+    "m7b5", "dim7", "maj7#5", "7#5", "7b5", "7sus4", "maj7#11", "maj9", "maj13", "9", "m9", "7b9",
+    "7#9", "7#11", "7b13", "7alt", "9sus4", "11", "m11", "13", "m13", "13sus4",
+];
+
+/// How well a scale on the root of a chord fits it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaleRecommendation {
+    pub scale: ScaleChoice,
+    /// The tensions (9, 11 and 13) of the chord that the scale does not contain.
+    pub missing_tensions: Vec<Degree>,
+    /// The degrees of the scale (counting from 0) that are not in the chord and lie a
+    /// semitone above one of its tones up to the seventh.
+    pub avoid_notes: Vec<usize>,
+}
+
+/// Ranks the scales that contain every chord tone up to the seventh of a chord, by
+/// how many of its tensions they are missing and then by their avoid notes. Scales
+/// with the same steps as an earlier one are left out.
+pub fn recommend_scales(
+    chord_degrees: &[Degree],
+    scales: &[ScaleChoice],
+) -> Vec<ScaleRecommendation> {
+    let chord_steps = chord_degrees
+        .iter()
+        .map(Degree::semitones)
+        .collect::<Vec<usize>>();
+    let chord_tone_steps = chord_degrees
+        .iter()
+        .filter(|degree| degree.number <= 7)
+        .map(Degree::semitones)
+        .collect::<Vec<usize>>();
+    let mut seen_steps: Vec<&[usize]> = Vec::new();
+    let mut recommendations = Vec::new();
+    for scale in scales {
+        let steps = scale.steps();
+        if seen_steps.contains(&steps) {
+            continue;
+        }
+        seen_steps.push(steps);
+        let contains = |degree: &Degree| steps.contains(&degree.semitones());
+        if !chord_tone_steps.iter().all(|step| steps.contains(step)) {
+            continue;
+        }
+        let missing_tensions = chord_degrees
+            .iter()
+            .filter(|degree| degree.number > 7 && !contains(degree))
+            .copied()
+            .collect();
+        let avoid_notes = get_avoid_notes(steps, &chord_steps, &chord_tone_steps);
+        recommendations.push(ScaleRecommendation {
+            scale: scale.clone(),
+            missing_tensions,
+            avoid_notes,
+        });
+    }
+    recommendations.sort_by_key(|recommendation| {
+        (
+            recommendation.missing_tensions.len(),
+            recommendation.avoid_notes.len(),
+        )
+    });
+    recommendations
+}
+
+/// Finds the chords of `CHORD_SUFFIXES` on the root of a scale whose tones are all in
+/// the scale, and whether the scale is the first recommendation for each of them.
+pub fn find_chords_for_scale(
+    root_note: SpelledNote,
+    steps: &[usize],
+    scales: &[ScaleChoice],
+) -> Vec<(Chord, bool)> {
+    CHORD_SUFFIXES
+        .iter()
+        .map(|suffix| parse_chord_symbol(&format!("{}{}", root_note, suffix)).unwrap())
+        .filter(|chord| chord.intervals().iter().all(|step| steps.contains(step)))
+        .map(|chord| {
+            let is_first_choice = recommend_scales(&chord.degrees, scales)
+                .first()
+                .is_some_and(|recommendation| recommendation.scale.steps() == steps);
+            (chord, is_first_choice)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::scales::{get_steps_by_scale, Scale};
+    use clap::ValueEnum;
+
+    fn catalog() -> Vec<ScaleChoice> {
+        Scale::value_variants()
+            .iter()
+            .copied()
+            .map(ScaleChoice::BuiltIn)
+            .collect()
+    }
+
+    #[test]
+    fn test_recommend_scales() {
+        let chord = parse_chord_symbol("G7alt").unwrap();
+        let recommendations = recommend_scales(&chord.degrees, &catalog());
+        assert_eq!(
+            recommendations[0].scale,
+            ScaleChoice::BuiltIn(Scale::Altered)
+        );
+        let half_diminished = recommendations
+            .iter()
+            .position(|recommendation| {
+                recommendation.scale == ScaleChoice::BuiltIn(Scale::HalfDiminished)
+            })
+            .unwrap();
+        assert_eq!(half_diminished, 1);
+        assert_eq!(
+            recommendations[half_diminished].missing_tensions,
+            vec![Degree::new(13, -1)]
+        );
+
+        let chord = parse_chord_symbol("Cmaj7").unwrap();
+        let recommendations = recommend_scales(&chord.degrees, &catalog());
+        assert_eq!(
+            recommendations[0].scale,
+            ScaleChoice::BuiltIn(Scale::Lydian)
+        );
+        let major = recommendations
+            .iter()
+            .find(|recommendation| recommendation.scale == ScaleChoice::BuiltIn(Scale::Major))
+            .unwrap();
+        assert_eq!(major.avoid_notes, vec![3]);
+        assert!(!recommendations
+            .iter()
+            .any(|recommendation| recommendation.scale == ScaleChoice::BuiltIn(Scale::Ionian)));
+    }
+
+    #[test]
+    fn test_find_chords_for_scale() {
+        let root = "C".parse().unwrap();
+        let chords = find_chords_for_scale(root, get_steps_by_scale(Scale::Lydian), &catalog());
+        let symbols = |first_choice: bool| {
+            chords
+                .iter()
+                .filter(|(_, is_first_choice)| *is_first_choice == first_choice)
+                .map(|(chord, _)| chord.symbol())
+                .collect::<Vec<String>>()
+        };
+        assert!(symbols(true).contains(&String::from("Cmaj7#11")));
+        assert!(symbols(false).contains(&String::from("C6/9")));
+        assert!(!chords.iter().any(|(chord, _)| chord.symbol() == "C7"));
+    }
+}
@@ -0,0 +1,668 @@
+use clap::ValueEnum;
+
+use super::notes::{Note, NOTES, NUM_NOTES};
+use super::spelling::{spell_degree, split_note_name, Degree, SpelledNote};
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum ChordType {
+    Triad,
+    Seventh,
+}
+
+pub const CHORD_TYPES: [ChordType; 2] = [ChordType::Triad, ChordType::Seventh];
+
+const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+pub const fn chord_type_to_string(chord_type: ChordType) -> &'static str {
+    match chord_type {
+        ChordType::Triad => "triads",
+        ChordType::Seventh => "seventh chords",
+    }
+}
+
+pub const fn get_num_notes_by_chord_type(chord_type: ChordType) -> usize {
+    match chord_type {
+        ChordType::Triad => 3,
+        ChordType::Seventh => 4,
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChordQuality {
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    MajorFlat5,
+    MajorSeventh,
+    DominantSeventh,
+    MinorSeventh,
+    MinorMajorSeventh,
+    HalfDiminishedSeventh,
+    DiminishedSeventh,
+    DiminishedMajorSeventh,
+    AugmentedMajorSeventh,
+    AugmentedSeventh,
+    DominantSeventhFlat5,
+    MajorSeventhFlat5,
+}
+
+pub const CHORD_QUALITIES: [ChordQuality; 16] = [
+    ChordQuality::Major,
+    ChordQuality::Minor,
+    ChordQuality::Diminished,
+    ChordQuality::Augmented,
+    ChordQuality::MajorFlat5,
+    ChordQuality::MajorSeventh,
+    ChordQuality::DominantSeventh,
+    ChordQuality::MinorSeventh,
+    ChordQuality::MinorMajorSeventh,
+    ChordQuality::HalfDiminishedSeventh,
+    ChordQuality::DiminishedSeventh,
+    ChordQuality::DiminishedMajorSeventh,
+    ChordQuality::AugmentedMajorSeventh,
+    ChordQuality::AugmentedSeventh,
+    ChordQuality::DominantSeventhFlat5,
+    ChordQuality::MajorSeventhFlat5,
+];
+
+pub const fn get_intervals_by_chord_quality(quality: ChordQuality) -> &'static [usize] {
+    match quality {
+        ChordQuality::Major => &[0, 4, 7],
+        ChordQuality::Minor => &[0, 3, 7],
+        ChordQuality::Diminished => &[0, 3, 6],
+        ChordQuality::Augmented => &[0, 4, 8],
+        ChordQuality::MajorFlat5 => &[0, 4, 6],
+        ChordQuality::MajorSeventh => &[0, 4, 7, 11],
+        ChordQuality::DominantSeventh => &[0, 4, 7, 10],
+        ChordQuality::MinorSeventh => &[0, 3, 7, 10],
+        ChordQuality::MinorMajorSeventh => &[0, 3, 7, 11],
+        ChordQuality::HalfDiminishedSeventh => &[0, 3, 6, 10],
+        ChordQuality::DiminishedSeventh => &[0, 3, 6, 9],
+        ChordQuality::DiminishedMajorSeventh => &[0, 3, 6, 11],
+        ChordQuality::AugmentedMajorSeventh => &[0, 4, 8, 11],
+        ChordQuality::AugmentedSeventh => &[0, 4, 8, 10],
+        ChordQuality::DominantSeventhFlat5 => &[0, 4, 6, 10],
+        ChordQuality::MajorSeventhFlat5 => &[0, 4, 6, 11],
+    }
+}
+
+/// Returns the suffix of a chord symbol, e.g. "m7" for a minor seventh chord.
+pub const fn chord_quality_to_symbol(quality: ChordQuality) -> &'static str {
+    match quality {
+        ChordQuality::Major => "",
+        ChordQuality::Minor => "m",
+        ChordQuality::Diminished => "dim",
+        ChordQuality::Augmented => "aug",
+        ChordQuality::MajorFlat5 => "(b5)",
+        ChordQuality::MajorSeventh => "maj7",
+        ChordQuality::DominantSeventh => "7",
+        ChordQuality::MinorSeventh => "m7",
+        ChordQuality::MinorMajorSeventh => "m(maj7)",
+        ChordQuality::HalfDiminishedSeventh => "m7b5",
+        ChordQuality::DiminishedSeventh => "dim7",
+        ChordQuality::DiminishedMajorSeventh => "dim(maj7)",
+        ChordQuality::AugmentedMajorSeventh => "maj7#5",
+        ChordQuality::AugmentedSeventh => "7#5",
+        ChordQuality::DominantSeventhFlat5 => "7b5",
+        ChordQuality::MajorSeventhFlat5 => "maj7b5",
+    }
+}
+
+/// Returns whether the roman numeral of a chord is in upper case, and the suffix
+/// that follows it, e.g. `(false, "ø7")` for a half diminished seventh chord.
+const fn chord_quality_to_numeral(quality: ChordQuality) -> (bool, &'static str) {
+    match quality {
+        ChordQuality::Major => (true, ""),
+        ChordQuality::Minor => (false, ""),
+        ChordQuality::Diminished => (false, "°"),
+        ChordQuality::Augmented => (true, "+"),
+        ChordQuality::MajorFlat5 => (true, "b5"),
+        ChordQuality::MajorSeventh => (true, "maj7"),
+        ChordQuality::DominantSeventh => (true, "7"),
+        ChordQuality::MinorSeventh => (false, "7"),
+        ChordQuality::MinorMajorSeventh => (false, "maj7"),
+        ChordQuality::HalfDiminishedSeventh => (false, "ø7"),
+        ChordQuality::DiminishedSeventh => (false, "°7"),
+        ChordQuality::DiminishedMajorSeventh => (false, "°maj7"),
+        ChordQuality::AugmentedMajorSeventh => (true, "+maj7"),
+        ChordQuality::AugmentedSeventh => (true, "+7"),
+        ChordQuality::DominantSeventhFlat5 => (true, "7b5"),
+        ChordQuality::MajorSeventhFlat5 => (true, "maj7b5"),
+    }
+}
+
+/// Classifies a chord from the steps of its notes, in any octave and order as long
+/// as the root comes first.
+pub fn get_chord_quality(chord_steps: &[usize]) -> Option<ChordQuality> {
+    let mut intervals = get_chord_intervals(chord_steps);
+    intervals.sort_unstable();
+    intervals.dedup();
+    CHORD_QUALITIES
+        .iter()
+        .find(|quality| get_intervals_by_chord_quality(**quality) == intervals)
+        .copied()
+}
+
+/// A chord with a spelled root, the degrees of its tones above the root and an
+/// optional bass note for slash chords.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chord {
+    pub root: SpelledNote,
+    /// The chord symbol without its root and bass, as written.
+    pub suffix: String,
+    pub degrees: Vec<Degree>,
+    pub bass: Option<SpelledNote>,
+}
+
+impl Chord {
+    /// Returns the semitones of the chord tones above the root, within an octave.
+    pub fn intervals(&self) -> Vec<usize> {
+        self.degrees.iter().map(Degree::semitones).collect()
+    }
+
+    pub fn notes(&self) -> Vec<Note> {
+        let root_index = NOTES.iter().position(|&n| n == self.root.note()).unwrap();
+        self.intervals()
+            .iter()
+            .map(|interval| NOTES[(root_index + interval) % NUM_NOTES])
+            .collect()
+    }
+
+    pub fn note_names(&self) -> Vec<SpelledNote> {
+        self.degrees
+            .iter()
+            .map(|degree| spell_degree(self.root, *degree))
+            .collect()
+    }
+
+    /// Returns the chord symbol, e.g. "Bm7" or "C/E". Chords of a known quality use
+    /// its usual suffix, others keep the suffix they were written with.
+    pub fn symbol(&self) -> String {
+        let suffix = match get_chord_quality(&self.intervals()) {
+            Some(quality) => chord_quality_to_symbol(quality),
+            None => &self.suffix,
+        };
+        match self.bass {
+            Some(bass) => format!("{}{}/{}", self.root, suffix, bass),
+            None => format!("{}{}", self.root, suffix),
+        }
+    }
+}
+
+/// Parses a chord symbol such as "F#m7b5", "Cmaj7#11", "Bb13sus4", "D7(b9,#11)"
+/// or "C/E".
+pub fn parse_chord_symbol(symbol: &str) -> Result<Chord, String> {
+    let symbol = symbol.trim();
+    let (chord, bass) = match symbol.rsplit_once('/') {
+        Some((chord, bass)) if bass.parse::<SpelledNote>().is_ok() => {
+            (chord, Some(bass.parse::<SpelledNote>()?))
+        }
+        _ => (symbol, None),
+    };
+    let (root, suffix) = split_note_name(chord);
+    let root = root.parse::<SpelledNote>()?;
+    Ok(Chord {
+        root,
+        suffix: suffix.to_string(),
+        degrees: parse_chord_suffix(suffix)?,
+        bass,
+    })
+}
+
+/// Returns the degrees of the chord tones for a chord symbol suffix. The suffix is
+/// read as a quality (m, dim, aug, ø, also written -, °, + ...), a major seventh
+/// mark (maj, M, Δ), an extension (6, 7, 9, 11, 13, 6/9) and any number of
+/// modifiers: sus2, sus4, add9, no3, alt and alterations such as b5, #9 or b13.
+fn parse_chord_suffix(suffix: &str) -> Result<Vec<Degree>, String> {
+    let invalid_suffix = || format!("\"{}\" is not a known chord quality", suffix);
+    let mut degrees = vec![Degree::new(1, 0), Degree::new(3, 0), Degree::new(5, 0)];
+    if suffix == "5" {
+        return Ok(vec![Degree::new(1, 0), Degree::new(5, 0)]);
+    }
+
+    let mut rest = suffix;
+    let (third, fifth, seventh) = if strip_any(&mut rest, &["maj", "Maj", "M", "Δ", "^"]).is_some()
+    {
+        (0, 0, Some(0))
+    } else if strip_any(&mut rest, &["min", "m", "-"]).is_some() {
+        (-1, 0, None)
+    } else if !rest.starts_with("omit") && strip_any(&mut rest, &["dim", "°", "o"]).is_some() {
+        (-1, -1, None)
+    } else if strip_any(&mut rest, &["aug", "+"]).is_some() {
+        (0, 1, None)
+    } else if strip_any(&mut rest, &["ø"]).is_some() {
+        (-1, -1, Some(-1))
+    } else {
+        (0, 0, None)
+    };
+    strip_any(&mut rest, &["("]);
+    let major_seventh =
+        seventh == Some(0) || strip_any(&mut rest, &["maj", "Maj", "M", "Δ", "^"]).is_some();
+    let seventh = if major_seventh {
+        0
+    } else if (third, fifth, seventh) == (-1, -1, None) {
+        -2
+    } else {
+        -1
+    };
+    degrees[1].alteration = third;
+    degrees[2].alteration = fifth;
+
+    let extension = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(rest, |index| &rest[..index]);
+    rest = &rest[extension.len()..];
+    let mut extension_degrees = match extension {
+        "" => vec![],
+        "6" if rest.starts_with("/9") => {
+            rest = &rest[2..];
+            vec![6, 9]
+        }
+        "6" => vec![6],
+        "69" => vec![6, 9],
+        "7" => vec![7],
+        "9" => vec![7, 9],
+        "11" => vec![7, 9, 11],
+        "13" if third == -1 => vec![7, 9, 11, 13],
+        "13" => vec![7, 9, 13],
+        _ => return Err(invalid_suffix()),
+    };
+    let has_seventh = extension_degrees.contains(&7);
+    if !has_seventh && (major_seventh && suffix.starts_with(['Δ', '^']) || suffix.starts_with('ø'))
+    {
+        extension_degrees.push(7);
+    }
+    for number in extension_degrees {
+        let alteration = if number == 7 { seventh } else { 0 };
+        degrees.push(Degree::new(number, alteration));
+    }
+
+    while !rest.is_empty() {
+        if strip_any(&mut rest, &["(", ")", ",", " "]).is_some() {
+            continue;
+        }
+        if let Some(sus) = strip_any(&mut rest, &["sus2", "sus4", "sus"]) {
+            let number = if sus == "sus2" { 2 } else { 4 };
+            degrees.retain(|degree| degree.number != 3);
+            degrees.push(Degree::new(number, 0));
+        } else if strip_any(&mut rest, &["add"]).is_some() {
+            let (number, alteration) = parse_chord_degree(&mut rest).ok_or_else(invalid_suffix)?;
+            degrees.retain(|degree| degree.number != number);
+            degrees.push(Degree::new(number, alteration));
+        } else if strip_any(&mut rest, &["no", "omit"]).is_some() {
+            let (number, _) = parse_chord_degree(&mut rest).ok_or_else(invalid_suffix)?;
+            degrees.retain(|degree| degree.number != number);
+        } else if strip_any(&mut rest, &["alt"]).is_some() {
+            degrees.retain(|degree| !matches!(degree.number, 5 | 9 | 11 | 13));
+            for (number, alteration) in [(9, -1), (9, 1), (11, 1), (13, -1)] {
+                degrees.push(Degree::new(number, alteration));
+            }
+        } else {
+            let (number, alteration) = parse_chord_degree(&mut rest)
+                .filter(|(number, alteration)| {
+                    *alteration != 0 && matches!(number, 5 | 9 | 11 | 13)
+                })
+                .ok_or_else(invalid_suffix)?;
+            // An altered fifth replaces the fifth, altered tensions are added next
+            // to each other, e.g. b9 and #9, but replace the natural one.
+            degrees.retain(|degree| {
+                degree.number != number || (number != 5 && degree.alteration != 0)
+            });
+            degrees.push(Degree::new(number, alteration));
+        }
+    }
+    degrees.sort_by_key(|degree| (degree.number, degree.alteration));
+    Ok(degrees)
+}
+
+/// Advances `rest` past the first of `prefixes` it starts with, and returns it.
+fn strip_any(rest: &mut &str, prefixes: &[&'static str]) -> Option<&'static str> {
+    let prefix = prefixes.iter().find(|prefix| rest.starts_with(**prefix))?;
+    *rest = &rest[prefix.len()..];
+    Some(prefix)
+}
+
+/// Reads a degree such as "9", "b13" or "#11" at the start of `rest`, accepting "-"
+/// and "+" for flats and sharps, and advances past it.
+fn parse_chord_degree(rest: &mut &str) -> Option<(usize, i32)> {
+    let alteration = match rest.chars().next()? {
+        'b' | '-' => -1,
+        '#' | '+' => 1,
+        _ => 0,
+    };
+    let digits = &rest[usize::from(alteration != 0)..];
+    let length = digits
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(digits.len());
+    let number = digits[..length].parse::<usize>().ok()?;
+    if !(1..=13).contains(&number) {
+        return None;
+    }
+    *rest = &digits[length..];
+    Some((number, alteration))
+}
+
+/// Parses a roman numeral such as "ii", "V7" or "bVImaj7" into the degree of its
+/// root above the tonic and a chord type. The case of the numeral and quality marks
+/// like "°" are ignored, as the chord is taken from the scale.
+pub fn parse_roman_numeral(numeral: &str) -> Option<(Degree, ChordType)> {
+    let accidental_length = numeral
+        .find(|c: char| c != 'b' && c != '#')
+        .unwrap_or(numeral.len());
+    let (accidental, numeral) = numeral.split_at(accidental_length);
+    let alteration = if accidental.starts_with('b') {
+        -(accidental.len() as i32)
+    } else {
+        accidental.len() as i32
+    };
+    if accidental.contains('b') && accidental.contains('#') {
+        return None;
+    }
+    let numeral_length = numeral
+        .find(|c: char| !matches!(c.to_ascii_uppercase(), 'I' | 'V'))
+        .unwrap_or(numeral.len());
+    let (numeral, suffix) = numeral.split_at(numeral_length);
+    let number = NUMERALS
+        .iter()
+        .position(|candidate| candidate.eq_ignore_ascii_case(numeral))?;
+    let chord_type = match suffix.trim_start_matches(['°', 'ø', '+']) {
+        "" => ChordType::Triad,
+        "7" | "maj7" => ChordType::Seventh,
+        _ => return None,
+    };
+    Some((Degree::new(number + 1, alteration), chord_type))
+}
+
+/// Finds the index of the note of a scale on the degree of a roman numeral. A
+/// numeral without an accidental also matches the only note on an altered degree
+/// with its number, like "III" in Dorian.
+pub fn find_roman_numeral_degree(scale_degrees: &[Degree], degree: Degree) -> Option<usize> {
+    scale_degrees
+        .iter()
+        .position(|scale_degree| *scale_degree == degree)
+        .or_else(|| {
+            let mut same_number = scale_degrees
+                .iter()
+                .enumerate()
+                .filter(|(_, scale_degree)| scale_degree.number == degree.number)
+                .map(|(index, _)| index);
+            match (degree.alteration, same_number.next(), same_number.next()) {
+                (0, Some(index), None) => Some(index),
+                _ => None,
+            }
+        })
+}
+
+/// A chord built on a degree of a scale, practiced as an arpeggio.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Arpeggio {
+    /// Index of the chord root within the harmony steps of the scale, counting from 0.
+    pub degree: usize,
+    pub chord_type: ChordType,
+    /// Steps of the chord tones above the root of the scale.
+    pub steps: Vec<usize>,
+}
+
+/// Stacks thirds on the `degree`-th note of a scale (counting from 0) using only
+/// notes of the scale. Every other scale note is preferred, falling back to the
+/// nearest scale note a third above, so that scales with more or fewer than seven
+/// notes still get tertian chords. Returns `None` when no third can be found.
+pub fn get_diatonic_chord_steps(
+    steps: &[usize],
+    degree: usize,
+    chord_type: ChordType,
+) -> Option<Vec<usize>> {
+    if degree >= steps.len() {
+        return None;
+    }
+    let mut chord_indices = vec![degree];
+    let mut span = 0;
+    while chord_indices.len() < get_num_notes_by_chord_type(chord_type) {
+        let index = *chord_indices.last().unwrap();
+        let interval_to = |next: usize| {
+            (steps[(index + next) % steps.len()] + NUM_NOTES - steps[index]) % NUM_NOTES
+        };
+        let next = std::iter::once(2)
+            .chain(1..steps.len())
+            .find(|next| matches!(interval_to(*next), 3 | 4))?;
+        span += interval_to(next);
+        if span >= NUM_NOTES {
+            return None;
+        }
+        chord_indices.push((index + next) % steps.len());
+    }
+    Some(chord_indices.iter().map(|index| steps[*index]).collect())
+}
+
+/// Returns the degrees (counting from 0) of a scale that have a chord of `chord_type`.
+pub fn get_degrees_with_chords(steps: &[usize], chord_type: ChordType) -> Vec<usize> {
+    (0..steps.len())
+        .filter(|degree| get_diatonic_chord_steps(steps, *degree, chord_type).is_some())
+        .collect()
+}
+
+/// Returns the intervals of a chord above its own root.
+pub fn get_chord_intervals(chord_steps: &[usize]) -> Vec<usize> {
+    chord_steps
+        .iter()
+        .map(|step| (step + NUM_NOTES - chord_steps[0]) % NUM_NOTES)
+        .collect()
+}
+
+/// Returns the roman numeral of a chord whose root is on `degree` above the tonic,
+/// e.g. "iiø7" or "bVI". Unknown chords are in lower case when their third is minor.
+pub fn get_roman_numeral(degree: Degree, chord_steps: &[usize]) -> String {
+    let (upper_case, suffix) = match get_chord_quality(chord_steps) {
+        Some(quality) => chord_quality_to_numeral(quality),
+        None => (!get_chord_intervals(chord_steps).contains(&3), ""),
+    };
+    let numeral = NUMERALS[(degree.number - 1) % NUMERALS.len()];
+    let numeral = if upper_case {
+        String::from(numeral)
+    } else {
+        numeral.to_lowercase()
+    };
+    let accidental = if degree.alteration < 0 { "b" } else { "#" };
+    format!(
+        "{}{}{}",
+        accidental.repeat(degree.alteration.unsigned_abs() as usize),
+        numeral,
+        suffix
+    )
+}
+
+/// Returns the chord symbol of a chord whose root is named `root`, e.g. "Bm7".
+/// Unknown chords are named after their intervals, e.g. "C(0 4 5)".
+pub fn get_chord_symbol(root: &str, chord_steps: &[usize]) -> String {
+    match get_chord_quality(chord_steps) {
+        Some(quality) => format!("{}{}", root, chord_quality_to_symbol(quality)),
+        None => format!(
+            "{}({})",
+            root,
+            get_chord_intervals(chord_steps)
+                .iter()
+                .map(|interval| interval.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DORIAN: [usize; 7] = [0, 2, 3, 5, 7, 9, 10];
+
+    #[test]
+    fn test_get_diatonic_chord_steps() {
+        assert_eq!(
+            get_diatonic_chord_steps(&DORIAN, 2, ChordType::Seventh),
+            Some(vec![3, 7, 10, 2])
+        );
+        assert_eq!(
+            get_diatonic_chord_steps(&DORIAN, 5, ChordType::Triad),
+            Some(vec![9, 0, 3])
+        );
+        assert_eq!(
+            get_diatonic_chord_steps(&[0, 2, 3, 4, 5, 7, 9, 10], 0, ChordType::Seventh),
+            Some(vec![0, 3, 7, 10])
+        );
+        assert_eq!(
+            get_diatonic_chord_steps(&[0, 2, 4, 7, 9], 1, ChordType::Triad),
+            None
+        );
+        assert_eq!(
+            get_degrees_with_chords(&[0, 2, 4, 7, 9], ChordType::Triad),
+            vec![0, 4]
+        );
+    }
+
+    #[test]
+    fn test_get_chord_quality() {
+        assert_eq!(
+            get_chord_quality(&[11, 2, 5, 9]),
+            Some(ChordQuality::HalfDiminishedSeventh)
+        );
+        assert_eq!(get_chord_quality(&[8, 0, 4]), Some(ChordQuality::Augmented));
+        assert_eq!(get_chord_quality(&[0, 4, 5]), None);
+    }
+
+    #[test]
+    fn test_get_roman_numeral() {
+        assert_eq!(get_roman_numeral(Degree::new(2, 0), &[2, 5, 9, 0]), "ii7");
+        assert_eq!(
+            get_roman_numeral(Degree::new(3, -1), &[3, 7, 10, 2]),
+            "bIIImaj7"
+        );
+        assert_eq!(get_roman_numeral(Degree::new(4, 0), &[5, 9, 0]), "IV");
+        assert_eq!(
+            get_roman_numeral(Degree::new(7, 0), &[11, 2, 5, 9]),
+            "viiø7"
+        );
+        assert_eq!(
+            get_roman_numeral(Degree::new(7, 0), &[11, 2, 5, 8]),
+            "vii°7"
+        );
+        assert_eq!(get_roman_numeral(Degree::new(6, 0), &[9, 0, 4]), "vi");
+        assert_eq!(get_roman_numeral(Degree::new(5, 1), &[8, 0, 4]), "#V+");
+    }
+
+    #[test]
+    fn test_parse_chord_symbol() {
+        let chord = parse_chord_symbol("F#m7b5").unwrap();
+        assert_eq!(chord.intervals(), vec![0, 3, 6, 10]);
+        assert_eq!(chord.notes(), vec![Note::FSharp, Note::A, Note::C, Note::E]);
+        assert_eq!(parse_chord_symbol("Bb-7").unwrap().symbol(), "Bbm7");
+        assert_eq!(parse_chord_symbol("C/E").unwrap().symbol(), "C/E");
+        assert!(parse_chord_symbol("H7").is_err());
+        assert!(parse_chord_symbol("Cfoo").is_err());
+    }
+
+    #[test]
+    fn test_parse_extended_chord_symbols() {
+        let names = |symbol: &str| {
+            parse_chord_symbol(symbol)
+                .unwrap()
+                .note_names()
+                .iter()
+                .map(|note| note.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        assert_eq!(names("Cmaj7#11"), "C E G B F#");
+        assert_eq!(names("Bb13sus4"), "Bb Eb F Ab C G");
+        assert_eq!(names("D7(b9,#11)"), "D F# A C Eb G#");
+        assert_eq!(names("C6/9"), "C E G A D");
+        assert_eq!(names("Ebdim7"), "Eb Gb Bbb Dbb");
+        assert_eq!(names("G5"), "G D");
+        assert_eq!(names("Comit3"), "C G");
+        assert_eq!(names("Cno3"), "C G");
+        assert_eq!(names("Cmomit5"), "C Eb");
+        assert_eq!(names("Cm(no5)"), "C Eb");
+        assert_eq!(names("C7omit3"), "C G Bb");
+        assert_eq!(names("Cdim7"), "C Eb Gb Bbb");
+        assert_eq!(parse_chord_symbol("Cmaj7#11").unwrap().symbol(), "Cmaj7#11");
+        assert_eq!(
+            parse_chord_symbol("C6/9/E")
+                .unwrap()
+                .bass
+                .unwrap()
+                .to_string(),
+            "E"
+        );
+    }
+
+    #[test]
+    fn test_parse_chord_quality_symbols() {
+        for quality in CHORD_QUALITIES.iter().copied() {
+            for root in ["F#", "Db"] {
+                let symbol = format!("{}{}", root, chord_quality_to_symbol(quality));
+                let chord = parse_chord_symbol(&symbol).unwrap();
+                assert_eq!(chord.intervals(), get_intervals_by_chord_quality(quality));
+                assert_eq!(chord.symbol(), symbol);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_roman_numeral() {
+        assert_eq!(
+            parse_roman_numeral("ii"),
+            Some((Degree::new(2, 0), ChordType::Triad))
+        );
+        assert_eq!(
+            parse_roman_numeral("V7"),
+            Some((Degree::new(5, 0), ChordType::Seventh))
+        );
+        assert_eq!(
+            parse_roman_numeral("viiø7"),
+            Some((Degree::new(7, 0), ChordType::Seventh))
+        );
+        assert_eq!(
+            parse_roman_numeral("bVImaj7"),
+            Some((Degree::new(6, -1), ChordType::Seventh))
+        );
+        assert_eq!(
+            parse_roman_numeral("#iv°"),
+            Some((Degree::new(4, 1), ChordType::Triad))
+        );
+        assert_eq!(parse_roman_numeral("Cmaj7"), None);
+        assert_eq!(parse_roman_numeral("VIII"), None);
+    }
+
+    #[test]
+    fn test_find_roman_numeral_degree() {
+        let dorian = [1, 2, 3, 4, 5, 6, 7]
+            .iter()
+            .zip([0, 0, -1, 0, 0, 0, -1])
+            .map(|(number, alteration)| Degree::new(*number, alteration))
+            .collect::<Vec<Degree>>();
+        assert_eq!(
+            find_roman_numeral_degree(&dorian, Degree::new(3, -1)),
+            Some(2)
+        );
+        assert_eq!(
+            find_roman_numeral_degree(&dorian, Degree::new(3, 0)),
+            Some(2)
+        );
+        assert_eq!(find_roman_numeral_degree(&dorian, Degree::new(3, 1)), None);
+        let major_pentatonic = [1, 2, 3, 5, 6].map(|number| Degree::new(number, 0));
+        assert_eq!(
+            find_roman_numeral_degree(&major_pentatonic, Degree::new(6, 0)),
+            Some(4)
+        );
+        assert_eq!(
+            find_roman_numeral_degree(&major_pentatonic, Degree::new(4, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_chord_symbol() {
+        assert_eq!(get_chord_symbol("B", &[2, 5, 9, 0]), "Bm7");
+        assert_eq!(get_chord_symbol("G", &[7, 11, 2, 5]), "G7");
+        assert_eq!(get_chord_symbol("C", &[0, 4, 5]), "C(0 4 5)");
+    }
+}
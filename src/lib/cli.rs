@@ -1,42 +1,183 @@
+use std::path::{Path, PathBuf};
+
 use chrono::{Datelike, Utc};
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
 use colored::Colorize;
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::{RngCore, SeedableRng};
 
-use super::fret_board::{FRET_SPAN, NUM_FRETS};
-use super::notes::{
-    accidental_to_note, note_to_string, Accidental, Note, FLAT_ACCIDENTALS, NOTES, NUM_NOTES,
+use super::analysis::{analyze_scale, step_size_to_string, ScaleAnalysis};
+use super::chord_scales::{find_chords_for_scale, recommend_scales};
+use super::chords::{
+    chord_type_to_string, find_roman_numeral_degree, get_chord_intervals, get_chord_symbol,
+    get_degrees_with_chords, get_diatonic_chord_steps, get_roman_numeral, parse_chord_symbol,
+    parse_roman_numeral, Arpeggio, Chord, ChordType, CHORD_TYPES,
+};
+use super::config::load_config;
+use super::edo::{
+    get_quarter_tone_steps_by_maqam, maqam_to_string, Edo, EdoScale, Maqam, MAX_EDO_DIVISIONS,
+    MIN_EDO_DIVISIONS,
+};
+use super::fret_board::{
+    build_fret_board, get_edo_starting_fret, get_pitch_at, Capo, FretBoardNotes, FRET_SPAN,
+    MAX_CAPO_FRET, NUM_FRETS,
+};
+use super::instruments::{get_default_categories, instrument_to_string, Instrument};
+use super::notes::{accidental_to_note, Accidental, Note, NOTES, NUM_NOTES};
+use super::pitch_classes::PitchClassSet;
+use super::pitches::DEFAULT_A4_FREQUENCY;
+use super::progressions::{get_progression_chord, split_progression, ProgressionChord};
+use super::relations::{get_scale_relations, ScaleRelations};
+use super::scala::{is_scala_file, load_scala_file, ScalaScale};
+use super::scale_search::{get_formula, search_scales, ScaleFilter, Symmetry, NUM_ROOTED_SETS};
+use super::scales::{
+    category_to_string, find_scales_containing, get_category_by_scale, get_scales_by_category,
+    parse_custom_scale, parse_interval, scale_to_string, CustomScale, Scale, ScaleCategory,
+    ScaleChoice,
+};
+use super::spelling::{
+    accidental_to_spelled_note, get_degree, get_degrees_by_steps, spell_root, spell_scale, Degree,
+    SpelledNote,
+};
+use super::tunings::{
+    get_first_fret_pitches, parse_tuning_notes, CustomTuning, Tuning, TuningChoice,
+};
+use super::voicings::{
+    build_chord_box, find_voicings, join_chord_boxes, Voicing, DEFAULT_MAX_STRETCH,
 };
-use super::scales::{get_steps_by_scale, scale_to_string, Scale, SCALES};
-use super::tunings::{tuning_to_string, Tuning};
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Labels {
+    Notes,
+    Degrees,
+    Intervals,
+    Semitones,
+}
+
+/// Where fret numbers are counted from when a capo is on.
+#[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum FretNumbering {
+    /// From the capo, which is then fret 0.
+    #[default]
+    Relative,
+    /// From the nut, as marked on the neck.
+    Absolute,
+}
 
 pub struct Format {
-    pub flat: bool,
     pub colored: bool,
+    pub labels: Labels,
 }
 
 pub struct Params {
-    pub tuning: Tuning,
-    pub root_note: Note,
-    pub scale: Scale,
+    pub tuning: TuningChoice,
+    pub capo: Capo,
+    pub root_note: SpelledNote,
+    pub scale: ScaleChoice,
     pub starting_fret: usize,
-    pub notes_in_scale: Vec<(Note, usize)>,
+    pub fret_board_notes: FretBoardNotes,
+    pub arpeggio: Option<Arpeggio>,
+    pub show_chords: bool,
+    pub voicing_chord: Option<Chord>,
+    pub max_stretch: usize,
+    /// A chord shown on the fret board instead of the scale.
+    pub chord: Option<Chord>,
+    /// The chords of a progression, each shown with its scale instead of the scale.
+    pub progression: Option<Vec<ProgressionChord>>,
+    /// The scale in an equal division of the octave, shown instead of the scale.
+    pub edo_scale: Option<EdoScale>,
+    pub relations: Option<ScaleRelations>,
+    /// The chords on the root that fit the scale, and whether it is their first choice.
+    pub chord_fits: Option<Vec<(Chord, bool)>>,
+    pub analysis: Option<ScaleAnalysis>,
+    /// Strings, counted from the thinnest starting at 1, and frets from the nut to
+    /// report the pitch of.
+    pub pitch_positions: Vec<(usize, usize)>,
+    pub a4_frequency: f64,
     pub format: Format,
 }
 
 pub fn get_params() -> Params {
     let Args {
         tuning,
+        instrument,
+        list_tunings,
         root_notes,
         scales,
+        custom_scales,
+        categories,
+        list_scales,
+        search_scales: search,
+        num_notes,
+        max_semitones,
+        with_degrees,
+        symmetry,
+        find_scales,
+        suggest_scales,
+        arpeggios,
+        degrees,
+        chords,
+        voicings,
+        max_stretch,
+        chord,
+        progression,
+        emphasize,
+        edo,
+        maqamat,
+        relations,
+        chord_fits,
+        analyze,
+        pitch_at,
+        a4,
         starting_frets,
+        capo,
+        fret_numbering,
         full_randomness,
         uncolored,
+        labels,
+        config,
         ..
     } = Args::parse();
 
+    if list_scales {
+        print_scale_catalog();
+        std::process::exit(0);
+    }
+
+    let config = load_config(config.as_deref()).unwrap_or_else(|err| exit_with_error(err));
+    let config_scales = config
+        .custom_scales()
+        .unwrap_or_else(|err| exit_with_error(err));
+
+    let config_tunings = config
+        .custom_tunings()
+        .unwrap_or_else(|err| exit_with_error(err));
+    if list_tunings {
+        print_tunings(&config_tunings);
+        std::process::exit(0);
+    }
+    let tuning = resolve_tuning(&tuning, instrument, &config_tunings)
+        .unwrap_or_else(|err| exit_with_error(err));
+
+    let catalog = Scale::value_variants()
+        .iter()
+        .copied()
+        .map(ScaleChoice::BuiltIn)
+        .chain(config_scales.iter().cloned().map(ScaleChoice::Custom))
+        .collect::<Vec<ScaleChoice>>();
+    let scale_filter = ScaleFilter {
+        num_notes,
+        max_consecutive_semitones: max_semitones,
+        steps: with_degrees.unwrap_or_default(),
+        symmetry,
+    };
+    if search {
+        print_scale_search(&scale_filter, &catalog);
+        std::process::exit(0);
+    }
+
     let mut rng: Box<dyn RngCore> = if full_randomness {
         Box::new(rand::thread_rng())
     } else {
@@ -44,88 +185,1093 @@ pub fn get_params() -> Params {
         Box::new(StdRng::seed_from_u64(seed))
     };
 
-    let tuning = tuning.unwrap();
-
-    let mut flat = false;
-    let root_note = if let Some(ref arg_notes) = root_notes {
+    let (root_note, root_spelling) = if let Some(ref arg_notes) = root_notes {
         let arg_note = arg_notes.choose(&mut rng).unwrap();
-        if FLAT_ACCIDENTALS.contains(arg_note) {
-            flat = true
-        }
-        accidental_to_note(arg_note)
+        (
+            accidental_to_note(arg_note),
+            Some(accidental_to_spelled_note(arg_note)),
+        )
     } else {
-        NOTES.choose(&mut rng).copied().unwrap()
+        (NOTES.choose(&mut rng).copied().unwrap(), None)
     };
     let format = Format {
-        flat,
         colored: !uncolored,
+        labels,
     };
 
-    let scale = if let Some(ref arg_scales) = scales {
-        arg_scales.choose(&mut rng).copied().unwrap()
+    let mut scala_scales: Vec<ScalaScale> = Vec::new();
+    let scale_pool: Vec<ScaleChoice> = if scales.is_some() || custom_scales.is_some() {
+        let built_in_scales = scales
+            .unwrap_or_default()
+            .into_iter()
+            .map(ScaleChoice::BuiltIn);
+        let custom_scales = custom_scales
+            .unwrap_or_default()
+            .iter()
+            .map(|arg_scale| {
+                if is_scala_file(arg_scale) {
+                    let scala_scale = load_scala_file(Path::new(arg_scale))
+                        .unwrap_or_else(|err| exit_with_error(err));
+                    let custom_scale = scala_scale.to_custom_scale();
+                    scala_scales.push(scala_scale);
+                    return ScaleChoice::Custom(custom_scale);
+                }
+                resolve_custom_scale(arg_scale, &config_scales)
+                    .map(ScaleChoice::Custom)
+                    .unwrap_or_else(|err| exit_with_error(err))
+            })
+            .collect::<Vec<ScaleChoice>>();
+        built_in_scales.chain(custom_scales).collect()
+    } else if scale_filter != ScaleFilter::default() {
+        let scale_pool = search_scales(&scale_filter, &catalog)
+            .iter()
+            .map(|result| result.to_scale_choice())
+            .collect::<Vec<ScaleChoice>>();
+        if scale_pool.is_empty() {
+            exit_with_error(String::from("No scales pass the search filters"));
+        }
+        scale_pool
     } else {
-        SCALES.choose(&mut rng).copied().unwrap()
+        // Scales from the config file have no category, so they are always included.
+        let categories = categories
+            .as_deref()
+            .unwrap_or_else(|| get_default_categories(tuning.instrument()));
+        Scale::value_variants()
+            .iter()
+            .copied()
+            .filter(|scale| categories.contains(&get_category_by_scale(*scale)))
+            .map(ScaleChoice::BuiltIn)
+            .chain(config_scales.iter().cloned().map(ScaleChoice::Custom))
+            .collect()
     };
+    if let Some(arg_notes) = find_scales {
+        let notes = parse_notes(&arg_notes).unwrap_or_else(|err| exit_with_error(err));
+        print_scales_containing(&notes, &scale_pool, format.colored);
+        std::process::exit(0);
+    }
+    if let Some(arg_chord) = suggest_scales {
+        let chord = parse_chord_symbol(&arg_chord).unwrap_or_else(|err| exit_with_error(err));
+        print_scales_for_chord(&chord, &scale_pool, format.colored);
+        std::process::exit(0);
+    }
 
-    let all_frets: Vec<usize> = (0..=NUM_FRETS - FRET_SPAN).collect();
+    let chord_type = arpeggios
+        .as_ref()
+        .map(|arg_chord_types| arg_chord_types.choose(&mut rng).copied().unwrap());
+    let scale_pool = if let Some(chord_type) = chord_type {
+        let scale_pool = scale_pool
+            .into_iter()
+            .filter(|scale| {
+                !get_arpeggio_degrees(&scale.harmony_steps(), chord_type, degrees.as_deref())
+                    .is_empty()
+            })
+            .collect::<Vec<ScaleChoice>>();
+        if scale_pool.is_empty() {
+            exit_with_error(String::from(
+                "None of the selected scales have arpeggios on the selected degrees",
+            ));
+        }
+        scale_pool
+    } else {
+        scale_pool
+    };
+    let scale = scale_pool.choose(&mut rng).cloned().unwrap();
+
+    let capo = Capo {
+        fret: capo.unwrap_or(0),
+        numbering: fret_numbering,
+    };
+    let num_frets = tuning.num_frets();
+    // Starting frets are counted from the capo from here on.
+    let starting_frets = starting_frets.map(|arg_frets| {
+        arg_frets
+            .into_iter()
+            .map(|arg_fret| {
+                let fret = capo.fret_from_number(arg_fret).unwrap_or_else(|| {
+                    exit_with_error(format!(
+                        "Starting fret {} is behind the capo at fret {}",
+                        arg_fret, capo.fret
+                    ))
+                });
+                if capo.fret + fret > num_frets - FRET_SPAN + 1 {
+                    exit_with_error(format!(
+                        "Starting fret {} is past the end of the {} neck, which has {} frets",
+                        arg_fret,
+                        instrument_to_string(tuning.instrument()).to_lowercase(),
+                        num_frets
+                    ));
+                }
+                fret
+            })
+            .collect::<Vec<usize>>()
+    });
+    let all_frets: Vec<usize> = (0..=num_frets - capo.fret - FRET_SPAN).collect();
     let starting_fret = if let Some(ref arg_frets) = starting_frets {
         arg_frets.choose(&mut rng).copied().unwrap()
     } else {
         all_frets.choose(&mut rng).copied().unwrap()
     };
 
-    let root_note_index = NOTES.iter().position(|&note| note == root_note).unwrap();
-    let steps = get_steps_by_scale(scale);
-    let notes_in_scale = steps
-        .iter()
-        .map(|step| {
-            let note_index = (root_note_index + *step) % NUM_NOTES;
-            (NOTES[note_index], *step)
+    let steps = scale.steps();
+    let root_note = root_spelling.unwrap_or_else(|| spell_root(root_note, steps));
+    let pitch_classes = PitchClassSet::from_steps(root_note.note(), steps);
+    let note_names = spell_scale(root_note, steps);
+
+    let num_strings = tuning.pitches().len();
+    let pitch_positions = pitch_at.unwrap_or_default();
+    // Frets are counted from the nut from here on.
+    let pitch_positions = pitch_positions
+        .into_iter()
+        .map(|(string, number)| {
+            let fret = capo.fret_from_number(number).unwrap_or_else(|| {
+                exit_with_error(format!(
+                    "Fret {} is behind the capo at fret {}",
+                    number, capo.fret
+                ))
+            });
+            (string, capo.fret + fret)
         })
-        .collect::<Vec<(Note, usize)>>();
+        .collect::<Vec<(usize, usize)>>();
+    if let Some((string, _)) = pitch_positions
+        .iter()
+        .find(|(string, _)| *string == 0 || *string > num_strings)
+    {
+        exit_with_error(format!(
+            "String {} does not exist in {} tuning",
+            string,
+            tuning.name()
+        ));
+    }
+    if let Some((string, fret)) = pitch_positions
+        .iter()
+        .find(|(string, fret)| get_pitch_at(&tuning, num_strings - string, *fret).is_none())
+    {
+        exit_with_error(format!(
+            "String {} does not reach fret {} in {} tuning",
+            string,
+            capo.fret_number(fret - capo.fret),
+            tuning.name()
+        ));
+    }
+
+    let relations = relations.then(|| get_scale_relations(steps, &catalog));
+    let chord_fits = chord_fits.then(|| find_chords_for_scale(root_note, steps, &catalog));
+
+    let analysis = analyze.then(|| analyze_scale(steps));
+
+    let voicing_chord = voicings.map(|arg_chord| {
+        resolve_chord(&arg_chord, &scale, &note_names).unwrap_or_else(|err| exit_with_error(err))
+    });
+    let chord = chord.map(|arg_chord| {
+        resolve_chord(&arg_chord, &scale, &note_names).unwrap_or_else(|err| exit_with_error(err))
+    });
+    let progression = progression.map(|arg_progression| {
+        let arg_chords = split_progression(&arg_progression);
+        if arg_chords.is_empty() {
+            exit_with_error(String::from("No chords were provided"));
+        }
+        arg_chords
+            .iter()
+            .map(|arg_chord| {
+                let chord = resolve_chord(arg_chord, &scale, &note_names)
+                    .unwrap_or_else(|err| exit_with_error(err));
+                // Roman numerals are in the key of the scale.
+                let key = parse_roman_numeral(arg_chord)
+                    .map(|_| PitchClassSet::from_steps(root_note.note(), &scale.harmony_steps()));
+                let mut progression_chord = get_progression_chord(chord, &catalog, key);
+                if let Some(ref numbers) = emphasize {
+                    progression_chord.fret_board_notes = progression_chord
+                        .fret_board_notes
+                        .emphasize_degrees(numbers);
+                }
+                progression_chord
+            })
+            .collect::<Vec<ProgressionChord>>()
+    });
+
+    let arpeggio = chord_type.map(|chord_type| {
+        let harmony_steps = scale.harmony_steps();
+        let degree = get_arpeggio_degrees(&harmony_steps, chord_type, degrees.as_deref())
+            .choose(&mut rng)
+            .copied()
+            .unwrap();
+        Arpeggio {
+            degree,
+            chord_type,
+            steps: get_diatonic_chord_steps(&harmony_steps, degree, chord_type).unwrap(),
+        }
+    });
+    let fret_board_notes = if let Some(ref chord) = chord {
+        FretBoardNotes::from_chord(chord)
+    } else if let Some(ref arpeggio) = arpeggio {
+        let chord_pitch_classes = PitchClassSet::from_steps(root_note.note(), &arpeggio.steps)
+            .with_root(
+                note_names[steps
+                    .iter()
+                    .position(|step| *step == arpeggio.steps[0])
+                    .unwrap()]
+                .note(),
+            );
+        let chord_note_names = chord_pitch_classes
+            .notes()
+            .into_iter()
+            .map(|note| note_names[pitch_classes.position(note).unwrap()])
+            .collect();
+        FretBoardNotes::new(chord_pitch_classes, chord_note_names)
+    } else {
+        FretBoardNotes::new(pitch_classes, note_names)
+    };
+    let fret_board_notes = match emphasize {
+        Some(ref numbers) => fret_board_notes.emphasize_degrees(numbers),
+        None => fret_board_notes,
+    };
+
+    let edo_scale = edo.map(|divisions| {
+        let edo = Edo::new(divisions);
+        if let Some(ref arg_maqamat) = maqamat {
+            let maqam = arg_maqamat.choose(&mut rng).copied().unwrap();
+            let steps = edo.map_quarter_tones(get_quarter_tone_steps_by_maqam(maqam));
+            EdoScale::new(edo, maqam_to_string(maqam), root_note, steps)
+        } else if let Some(scala_scale) = scala_scales
+            .iter()
+            .find(|scala_scale| ScaleChoice::Custom(scala_scale.to_custom_scale()) == scale)
+        {
+            EdoScale::from_cents(edo, &scala_scale.name, root_note, &scala_scale.note_cents())
+        } else {
+            EdoScale::new(
+                edo,
+                scale.name(),
+                root_note,
+                edo.map_scale(root_note, steps),
+            )
+        }
+    });
 
     Params {
         tuning,
+        capo,
         root_note,
         scale,
         starting_fret,
-        notes_in_scale,
+        fret_board_notes,
+        arpeggio,
+        show_chords: chords,
+        voicing_chord,
+        max_stretch,
+        chord,
+        progression,
+        edo_scale,
+        relations,
+        chord_fits,
+        analysis,
+        pitch_positions,
+        a4_frequency: a4,
         format,
     }
 }
 
+/// Returns the degrees (counting from 0) of a scale to pick an arpeggio from, limited
+/// to the 1-based `arg_degrees` if provided.
+fn get_arpeggio_degrees(
+    steps: &[usize],
+    chord_type: ChordType,
+    arg_degrees: Option<&[usize]>,
+) -> Vec<usize> {
+    get_degrees_with_chords(steps, chord_type)
+        .into_iter()
+        .filter(|degree| arg_degrees.is_none_or(|arg_degrees| arg_degrees.contains(&(degree + 1))))
+        .collect()
+}
+
+/// Resolves a chord given either as a chord symbol or as a roman numeral of the scale.
+fn resolve_chord(
+    arg_chord: &str,
+    scale: &ScaleChoice,
+    note_names: &[SpelledNote],
+) -> Result<Chord, String> {
+    let Some((degree, chord_type)) = parse_roman_numeral(arg_chord) else {
+        return parse_chord_symbol(arg_chord);
+    };
+    let steps = scale.steps();
+    let no_chord = || format!("{} has no {} chord", scale.name(), arg_chord);
+    let harmony_steps = scale.harmony_steps();
+    let scale_degrees = harmony_steps
+        .iter()
+        .map(|step| {
+            let note = note_names[steps.iter().position(|s| s == step).unwrap()];
+            get_degree(note_names[0], note, *step)
+        })
+        .collect::<Vec<Degree>>();
+    let index = find_roman_numeral_degree(&scale_degrees, degree).ok_or_else(no_chord)?;
+    let chord_steps =
+        get_diatonic_chord_steps(&harmony_steps, index, chord_type).ok_or_else(no_chord)?;
+    let note_at = |step: &usize| note_names[steps.iter().position(|s| s == step).unwrap()];
+    let root = note_at(&chord_steps[0]);
+    let degrees = chord_steps
+        .iter()
+        .zip(get_chord_intervals(&chord_steps))
+        .map(|(step, interval)| get_degree(root, note_at(step), interval))
+        .collect();
+    Ok(Chord {
+        root,
+        suffix: get_chord_symbol("", &chord_steps),
+        degrees,
+        bass: None,
+    })
+}
+
+/// Resolves a `--custom-scales` entry, which is either `NAME=FORMULA` or the name
+/// of a scale defined in the config file.
+fn resolve_custom_scale(
+    arg_scale: &str,
+    config_scales: &[CustomScale],
+) -> Result<CustomScale, String> {
+    if arg_scale.contains('=') {
+        return parse_custom_scale(arg_scale);
+    }
+    config_scales
+        .iter()
+        .find(|config_scale| config_scale.name.eq_ignore_ascii_case(arg_scale.trim()))
+        .cloned()
+        .ok_or(format!(
+            "Scale \"{}\" is not defined in the config file",
+            arg_scale
+        ))
+}
+
+/// Resolves a `--tuning`, which is either a built-in tuning, the name of a tuning
+/// defined in the config file or the notes of its strings from the thickest, played
+/// on `--instrument`. Built-in and config file tunings already know their
+/// instrument.
+fn resolve_tuning(
+    arg_tuning: &str,
+    arg_instrument: Option<Instrument>,
+    config_tunings: &[CustomTuning],
+) -> Result<TuningChoice, String> {
+    let known_tuning = Tuning::from_str(arg_tuning, true)
+        .ok()
+        .map(TuningChoice::BuiltIn)
+        .or_else(|| {
+            config_tunings
+                .iter()
+                .find(|config_tuning| config_tuning.name.eq_ignore_ascii_case(arg_tuning.trim()))
+                .map(|config_tuning| TuningChoice::Custom(config_tuning.clone()))
+        });
+    if let Some(tuning) = known_tuning {
+        return match arg_instrument {
+            Some(instrument) if instrument != tuning.instrument() => Err(format!(
+                "{} tuning is played on {}, not on {}; --instrument only applies to tunings given as notes",
+                tuning.name(),
+                instrument_to_string(tuning.instrument()).to_lowercase(),
+                instrument_to_string(instrument).to_lowercase()
+            )),
+            _ => Ok(tuning),
+        };
+    }
+    let instrument = arg_instrument.unwrap_or(Instrument::Guitar);
+    let notes = arg_tuning
+        .split([' ', ','])
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let pitches = parse_tuning_notes(&notes, instrument).map_err(|err| {
+        format!(
+            "Tuning \"{}\" is not a built-in tuning (see --list-tunings), a tuning from the config file or a list of notes ({})",
+            arg_tuning, err
+        )
+    })?;
+    Ok(TuningChoice::Custom(CustomTuning {
+        name: notes,
+        pitches,
+        instrument,
+    }))
+}
+
+/// Parses a list of note names separated by spaces or commas, e.g. "C E G Bb D".
+fn parse_notes(arg_notes: &str) -> Result<Vec<Note>, String> {
+    let mut notes = Vec::new();
+    for note_name in arg_notes.split([' ', ',']).filter(|name| !name.is_empty()) {
+        let note = note_name.parse::<SpelledNote>()?.note();
+        if !notes.contains(&note) {
+            notes.push(note);
+        }
+    }
+    if notes.is_empty() {
+        return Err(String::from("No notes were provided"));
+    }
+    Ok(notes)
+}
+
+fn print_scales_containing(notes: &[Note], scale_pool: &[ScaleChoice], colored: bool) {
+    let matches = find_scales_containing(notes, scale_pool);
+    if matches.is_empty() {
+        println!("No scales contain all of these notes");
+        return;
+    }
+    for (root, scale) in matches {
+        let root_note = spell_root(root, scale.steps());
+        let added_notes = spell_scale(root_note, scale.steps())
+            .iter()
+            .filter(|note| !notes.contains(&note.note()))
+            .map(|note| note.to_string())
+            .collect::<Vec<String>>();
+        println!(
+            "{} {} ({})",
+            format_with_color(&root_note.to_string(), 0, colored),
+            scale.name(),
+            if added_notes.is_empty() {
+                String::from("adds no notes")
+            } else {
+                format!("adds {}", added_notes.join(", "))
+            }
+        );
+    }
+}
+
+fn print_scales_for_chord(chord: &Chord, scale_pool: &[ScaleChoice], colored: bool) {
+    let recommendations = recommend_scales(&chord.degrees, scale_pool);
+    if recommendations.is_empty() {
+        println!("No scales contain the tones of {}", chord.symbol());
+        return;
+    }
+    for recommendation in recommendations {
+        let steps = recommendation.scale.steps();
+        let notes = spell_scale(chord.root, steps);
+        let degrees = get_degrees_by_steps(steps);
+        let mut remarks = Vec::new();
+        if !recommendation.missing_tensions.is_empty() {
+            remarks.push(format!(
+                "lacks {}",
+                recommendation
+                    .missing_tensions
+                    .iter()
+                    .map(|degree| degree.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        if !recommendation.avoid_notes.is_empty() {
+            remarks.push(format!(
+                "avoid {}",
+                recommendation
+                    .avoid_notes
+                    .iter()
+                    .map(|degree| format!(
+                        "{} ({})",
+                        format_with_color(&notes[*degree].to_string(), steps[*degree], colored),
+                        degrees[*degree]
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        println!(
+            "{} {} ({})",
+            format_with_color(&chord.root.to_string(), 0, colored),
+            recommendation.scale.name(),
+            if remarks.is_empty() {
+                String::from("fits every chord tone")
+            } else {
+                remarks.join("; ")
+            }
+        );
+    }
+}
+
+fn print_chord_fits(chord_fits: &[(Chord, bool)], colored: bool) {
+    let list_or_none = |first_choice: bool| {
+        let chords = chord_fits
+            .iter()
+            .filter(|(_, is_first_choice)| *is_first_choice == first_choice)
+            .map(|(chord, _)| format_with_color(&chord.symbol(), 0, colored))
+            .collect::<Vec<String>>();
+        if chords.is_empty() {
+            String::from("none")
+        } else {
+            chords.join(", ")
+        }
+    };
+    println!(
+        "This scale is the first choice over: {}",
+        list_or_none(true)
+    );
+    println!("This scale also fits: {}", list_or_none(false));
+}
+
+fn print_progression(
+    tuning: &TuningChoice,
+    capo: &Capo,
+    starting_fret: usize,
+    progression: &[ProgressionChord],
+    format: &Format,
+) {
+    let Format { colored, .. } = *format;
+    println!(
+        "Here's the progression {} starting at fret {} in {}",
+        progression
+            .iter()
+            .map(|progression_chord| format_with_color(
+                &progression_chord.chord.symbol(),
+                0,
+                colored
+            ))
+            .collect::<Vec<String>>()
+            .join(" – "),
+        capo.fret_number(starting_fret),
+        format_tuning(tuning, capo),
+    );
+    for ProgressionChord {
+        chord,
+        scale,
+        fret_board_notes,
+    } in progression
+    {
+        println!();
+        for string in build_fret_board(tuning, capo, starting_fret, fret_board_notes, format) {
+            println!("{}", string);
+        }
+        println!(
+            "{} over {}: {}",
+            format_with_color(&chord.symbol(), 0, colored),
+            match scale {
+                Some(scale) => format!(
+                    "{} {}",
+                    format_with_color(&chord.root.to_string(), 0, colored),
+                    scale.name()
+                ),
+                None => String::from("its chord tones"),
+            },
+            format_note_names(fret_board_notes, colored)
+        );
+    }
+}
+
+fn print_relations(
+    root_note: SpelledNote,
+    scale: &ScaleChoice,
+    relations: &ScaleRelations,
+    colored: bool,
+) {
+    let notes = spell_scale(root_note, scale.steps());
+    let join_names = |scales: &[ScaleChoice]| {
+        scales
+            .iter()
+            .map(ScaleChoice::name)
+            .collect::<Vec<&str>>()
+            .join(" / ")
+    };
+    let list_or_none = |items: Vec<String>| {
+        if items.is_empty() {
+            String::from("none")
+        } else {
+            items.join(", ")
+        }
+    };
+
+    println!(
+        "The relative modes of this scale are: {}",
+        list_or_none(
+            relations
+                .modes
+                .iter()
+                .map(|(degree, scales)| format!(
+                    "{} {}",
+                    format_with_color(&notes[*degree].to_string(), scale.steps()[*degree], colored),
+                    join_names(scales)
+                ))
+                .collect()
+        )
+    );
+    println!(
+        "The parallel modes of this scale are: {}",
+        list_or_none(
+            relations
+                .modes
+                .iter()
+                .map(|(_, scales)| format!(
+                    "{} {}",
+                    format_with_color(&root_note.to_string(), 0, colored),
+                    join_names(scales)
+                ))
+                .collect()
+        )
+    );
+    if let Some((parent, degree, mode)) = &relations.parent {
+        println!(
+            "The parent scale is: {} {} (mode {})",
+            format_with_color(&notes[*degree].to_string(), scale.steps()[*degree], colored),
+            parent.name(),
+            mode
+        );
+    }
+    println!(
+        "The neighboring scales are: {}",
+        list_or_none(
+            relations
+                .neighbors
+                .iter()
+                .map(|(neighbor, removed, added)| format!(
+                    "{} {} ({} instead of {})",
+                    format_with_color(&root_note.to_string(), 0, colored),
+                    neighbor.name(),
+                    added,
+                    removed
+                ))
+                .collect()
+        )
+    );
+}
+
+fn print_analysis(
+    root_note: SpelledNote,
+    scale: &ScaleChoice,
+    analysis: &ScaleAnalysis,
+    colored: bool,
+) {
+    let steps = scale.steps();
+    let notes = spell_scale(root_note, steps);
+    println!(
+        "The step pattern of this scale is: {}",
+        analysis
+            .step_sizes
+            .iter()
+            .map(|step_size| step_size_to_string(*step_size))
+            .collect::<Vec<String>>()
+            .join("-")
+    );
+    println!(
+        "The interval vector of this scale is: <{}>",
+        analysis
+            .interval_vector
+            .iter()
+            .map(|count| count.to_string())
+            .collect::<String>()
+    );
+    println!(
+        "The Forte number of this scale is: {} (prime form [{}])",
+        analysis.forte_number.as_deref().unwrap_or("none"),
+        analysis
+            .prime_form
+            .iter()
+            .map(|step| step.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    if analysis.num_distinct_transpositions < NUM_NOTES {
+        println!(
+            "This scale is symmetric and only has {} distinct transpositions",
+            analysis.num_distinct_transpositions
+        );
+    } else {
+        println!("This scale is not symmetric and has 12 distinct transpositions");
+    }
+    println!(
+        "The number of tritones in this scale is: {}",
+        analysis.num_tritones
+    );
+    if let Some(ref chord_steps) = analysis.tonic_chord_steps {
+        println!(
+            "The avoid notes over the tonic chord ({}) are: {}",
+            format_with_color(
+                &get_chord_symbol(&root_note.to_string(), chord_steps),
+                0,
+                colored
+            ),
+            if analysis.avoid_notes.is_empty() {
+                String::from("none")
+            } else {
+                let degrees = get_degrees_by_steps(steps);
+                analysis
+                    .avoid_notes
+                    .iter()
+                    .map(|degree| {
+                        format!(
+                            "{} ({})",
+                            format_with_color(&notes[*degree].to_string(), steps[*degree], colored),
+                            degrees[*degree]
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }
+        );
+    }
+}
+
+fn print_scale_catalog() {
+    for category in ScaleCategory::value_variants() {
+        println!(
+            "{} ({}):",
+            category_to_string(*category),
+            category.to_possible_value().unwrap().get_name()
+        );
+        for scale in get_scales_by_category(*category) {
+            println!(
+                "    {:<28}{}",
+                scale.to_possible_value().unwrap().get_name(),
+                scale_to_string(scale)
+            );
+        }
+    }
+}
+
+fn print_scale_search(scale_filter: &ScaleFilter, catalog: &[ScaleChoice]) {
+    let results = search_scales(scale_filter, catalog);
+    for result in &results {
+        let forte_number = PitchClassSet::from_steps(Note::C, &result.steps).forte_number();
+        println!(
+            "    {:<30}{:<8}{}",
+            get_formula(&result.steps),
+            forte_number.as_deref().unwrap_or(""),
+            result
+                .names
+                .iter()
+                .map(|scale| scale.name())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+    }
+    println!(
+        "{} of the {} scales containing the root pass the search filters",
+        results.len(),
+        NUM_ROOTED_SETS
+    );
+}
+
+/// Lists the built-in and config file tunings with their value, name and pitches,
+/// sizing each column to its longest entry.
+fn print_tunings(config_tunings: &[CustomTuning]) {
+    let row = |value: &str, tuning: &TuningChoice| {
+        let pitches = tuning
+            .pitches()
+            .iter()
+            .map(|pitch| pitch.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        [String::from(value), tuning.name(), pitches]
+    };
+    let built_in_rows = Tuning::value_variants()
+        .iter()
+        .map(|tuning| {
+            row(
+                tuning.to_possible_value().unwrap().get_name(),
+                &TuningChoice::BuiltIn(*tuning),
+            )
+        })
+        .collect::<Vec<[String; 3]>>();
+    let config_rows = config_tunings
+        .iter()
+        .map(|config_tuning| {
+            row(
+                &config_tuning.name,
+                &TuningChoice::Custom(config_tuning.clone()),
+            )
+        })
+        .collect::<Vec<[String; 3]>>();
+    let column_width = |column: usize| {
+        built_in_rows
+            .iter()
+            .chain(&config_rows)
+            .map(|row| row[column].chars().count())
+            .max()
+            .unwrap_or(0)
+            + 2
+    };
+    let (value_width, name_width) = (column_width(0), column_width(1));
+    let print_rows = |rows: &[[String; 3]]| {
+        for [value, name, pitches] in rows {
+            println!(
+                "    {:<value_width$}{:<name_width$}{}",
+                value, name, pitches
+            );
+        }
+    };
+    println!("Built-in tunings:");
+    print_rows(&built_in_rows);
+    if !config_rows.is_empty() {
+        println!("Config file tunings:");
+        print_rows(&config_rows);
+    }
+}
+
+/// Names the tuning for the headers, e.g. "Standard E (6 string) tuning with a capo at fret 2".
+fn format_tuning(tuning: &TuningChoice, capo: &Capo) -> String {
+    if capo.fret == 0 {
+        format!("{} tuning", tuning.name())
+    } else {
+        format!("{} tuning with a capo at fret {}", tuning.name(), capo.fret)
+    }
+}
+
+/// Parses a position on the fret board written as `STRING:FRET`, e.g. "6:5".
+fn parse_string_and_fret(position: &str) -> Result<(usize, usize), String> {
+    let invalid_position = || format!("\"{}\" is not in the form STRING:FRET", position);
+    let (string, fret) = position.split_once(':').ok_or_else(invalid_position)?;
+    let string = string
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| invalid_position())?;
+    let fret = fret
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| invalid_position())?;
+    if fret > NUM_FRETS {
+        return Err(format!("Fret must be <= {}", NUM_FRETS));
+    }
+    Ok((string, fret))
+}
+
+fn exit_with_error(message: String) -> ! {
+    Args::command()
+        .error(ErrorKind::InvalidValue, message)
+        .exit()
+}
+
 pub fn print_output(params: Params, fret_board: Vec<String>) {
     let Params {
-        tuning,
+        ref tuning,
+        ref capo,
         root_note,
-        scale,
+        ref scale,
         starting_fret,
         ref format,
-        ref notes_in_scale,
+        ref fret_board_notes,
+        ref arpeggio,
+        show_chords,
+        ref voicing_chord,
+        max_stretch,
+        ref chord,
+        ref progression,
+        ref edo_scale,
+        ref relations,
+        ref chord_fits,
+        ref analysis,
+        ref pitch_positions,
+        a4_frequency,
         ..
     } = params;
-    let Format { flat, colored } = *format;
+    let Format { colored, .. } = *format;
+
+    if let Some(progression) = progression {
+        print_progression(tuning, capo, starting_fret, progression, format);
+    } else {
+        for string in fret_board {
+            println!("{}", string);
+        }
+
+        if let Some(Arpeggio { steps, .. }) = arpeggio {
+            println!(
+                "Here's the arpeggio of the day: {} ({}) of {} {} starting at fret {} in {}",
+                get_roman_numeral(
+                    get_degree(root_note, fret_board_notes.names[0], steps[0]),
+                    steps
+                ),
+                format_with_color(
+                    &get_chord_symbol(&fret_board_notes.names[0].to_string(), steps),
+                    0,
+                    colored
+                ),
+                format_with_color(&root_note.to_string(), 0, colored),
+                scale.name(),
+                capo.fret_number(starting_fret),
+                format_tuning(tuning, capo),
+            );
+        } else if let Some(chord) = chord {
+            println!(
+                "Here are the tones of {} starting at fret {} in {}",
+                format_with_color(&chord.symbol(), 0, colored),
+                capo.fret_number(starting_fret),
+                format_tuning(tuning, capo),
+            );
+        } else if let Some(edo_scale) = edo_scale {
+            println!(
+                "Here's the scale of the day: {} {} in {}-EDO starting at fret {} in {}",
+                format_with_color(&root_note.to_string(), 0, colored),
+                edo_scale.name,
+                edo_scale.edo.divisions,
+                capo.in_edo(edo_scale.edo)
+                    .fret_number(get_edo_starting_fret(edo_scale.edo, capo, starting_fret)),
+                format_tuning(tuning, capo),
+            );
+        } else {
+            println!(
+                "Here's the scale of the day: {} {} starting at fret {} in {}",
+                format_with_color(&root_note.to_string(), 0, colored),
+                scale.name(),
+                capo.fret_number(starting_fret),
+                format_tuning(tuning, capo),
+            );
+        }
 
-    for string in fret_board {
-        println!("{}", string);
+        println!(
+            "The notes in this {} are: {}",
+            if arpeggio.is_some() {
+                "arpeggio"
+            } else if chord.is_some() {
+                "chord"
+            } else {
+                "scale"
+            },
+            match edo_scale {
+                Some(edo_scale) => edo_scale
+                    .names
+                    .iter()
+                    .zip(&edo_scale.steps)
+                    .map(|(name, step)| format_with_color(
+                        name,
+                        edo_scale.edo.nearest_twelve_tone_step(*step),
+                        colored
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                None => format_note_names(fret_board_notes, colored),
+            }
+        );
     }
 
-    println!(
-        "Here's the scale of the day: {} {} starting at fret {} in {} tuning",
-        format_with_color(note_to_string(root_note, flat), 0, colored),
-        scale_to_string(scale),
-        starting_fret,
-        tuning_to_string(tuning),
-    );
+    if show_chords {
+        let steps = scale.harmony_steps();
+        let notes = spell_scale(root_note, &steps);
+        for chord_type in CHORD_TYPES {
+            let chords = (0..steps.len())
+                .filter_map(|degree| {
+                    let chord_steps = get_diatonic_chord_steps(&steps, degree, chord_type)?;
+                    Some(format!(
+                        "{} – {}",
+                        get_roman_numeral(
+                            get_degree(root_note, notes[degree], steps[degree]),
+                            &chord_steps
+                        ),
+                        get_chord_symbol(&notes[degree].to_string(), &chord_steps)
+                    ))
+                })
+                .collect::<Vec<String>>();
+            println!(
+                "The {} in this scale are: {}",
+                chord_type_to_string(chord_type),
+                if chords.is_empty() {
+                    String::from("none")
+                } else {
+                    chords.join(", ")
+                }
+            );
+        }
+    }
 
-    println!(
-        "The notes in this scale are: {}",
-        notes_in_scale
+    if let Some(relations) = relations {
+        print_relations(root_note, scale, relations, colored);
+    }
+
+    if let Some(chord_fits) = chord_fits {
+        print_chord_fits(chord_fits, colored);
+    }
+
+    if let Some(analysis) = analysis {
+        print_analysis(root_note, scale, analysis, colored);
+    }
+
+    if let Some(chord) = voicing_chord {
+        let bass = chord.bass.unwrap_or(chord.root).note();
+        let mut chord_notes = chord.notes();
+        if !chord_notes.contains(&bass) {
+            chord_notes.push(bass);
+        }
+        let first_frets = tuning
+            .first_frets()
             .iter()
-            .map(|(note, step)| format_with_color(note_to_string(*note, flat), *step, colored))
-            .collect::<Vec<String>>()
-            .join(", ")
-    );
+            .map(|first_fret| first_fret.saturating_sub(capo.fret))
+            .collect::<Vec<usize>>();
+        let voicings = find_voicings(
+            &get_first_fret_pitches(tuning, capo.fret),
+            &first_frets,
+            starting_fret,
+            &chord_notes,
+            bass,
+            max_stretch,
+        );
+        // Chord boxes count frets the same way as the fret board.
+        let voicings = voicings
+            .into_iter()
+            .map(|voicing| Voicing {
+                frets: voicing
+                    .frets
+                    .iter()
+                    .map(|fret| fret.map(|fret| capo.fret_number(fret)))
+                    .collect(),
+            })
+            .collect::<Vec<Voicing>>();
+        let starting_fret = capo.fret_number(starting_fret);
+        let last_fret = starting_fret + FRET_SPAN - 1;
+        if voicings.is_empty() {
+            println!(
+                "No voicings of {} found between frets {} and {}",
+                chord.symbol(),
+                starting_fret,
+                last_fret
+            );
+        } else {
+            println!(
+                "Voicings of {} between frets {} and {}:",
+                chord.symbol(),
+                starting_fret,
+                last_fret
+            );
+            let chord_boxes = voicings
+                .iter()
+                .map(|voicing| build_chord_box(voicing, starting_fret, &chord.symbol()))
+                .collect::<Vec<Vec<String>>>();
+            for line in join_chord_boxes(&chord_boxes) {
+                println!("{}", line);
+            }
+        }
+    }
+
+    let num_strings = tuning.pitches().len();
+    for (string, fret) in pitch_positions {
+        let pitch = get_pitch_at(tuning, num_strings - string, *fret).unwrap();
+        println!(
+            "The pitch at string {}, fret {} is {} (MIDI {}, {:.2} Hz)",
+            string,
+            capo.fret_number(fret - capo.fret),
+            pitch,
+            pitch.midi_number(),
+            pitch.frequency(a4_frequency)
+        );
+    }
+}
+
+/// Makes an emphasized note stand out in bold and dims the others. Without color,
+/// emphasized notes are put in brackets instead.
+pub fn format_with_emphasis(
+    note_string: &str,
+    step: usize,
+    emphasized: bool,
+    colored: bool,
+) -> String {
+    match (emphasized, colored) {
+        (true, true) => format!("{}", format_with_color(note_string, step, colored).bold()),
+        (true, false) => format!("[{}]", note_string),
+        (false, true) => format!("{}", note_string.dimmed()),
+        (false, false) => String::from(note_string),
+    }
+}
+
+/// Lists the names of the notes on the fret board, emphasized like on the fret board.
+fn format_note_names(notes: &FretBoardNotes, colored: bool) -> String {
+    notes
+        .names
+        .iter()
+        .zip(notes.pitch_classes.steps())
+        .zip(&notes.emphasized)
+        .map(|((note, step), emphasized)| {
+            if notes.has_emphasis() {
+                format_with_emphasis(&note.to_string(), step, *emphasized, colored)
+            } else {
+                format_with_color(&note.to_string(), step, colored)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
 }
 
 pub fn format_with_color(note_string: &str, step: usize, colored: bool) -> String {
@@ -150,14 +1296,29 @@ pub fn format_with_color(note_string: &str, step: usize, colored: bool) -> Strin
 #[command(name = "daily-scale", version, about = "Have you practiced today?", long_about = None)]
 struct Args {
     #[arg(
-        value_enum,
         required = false,
         short = 't',
         long,
         default_value = "standard-e6",
-        help = "Select the tuning you want to play in"
+        help = "Select the tuning you want to play in: a built-in tuning (see --list-tunings), the name of a tuning from the config file, or the notes of the strings from the thickest (\"D A D G A D\" or \"E2 A2 D3 G3 B3 E4\"), where a note without an octave is the lowest one above the previous string"
+    )]
+    tuning: String,
+
+    #[arg(
+        value_enum,
+        required = false,
+        short = 'i',
+        long,
+        help = "Select the instrument a tuning given as notes is played on, which sets its number of frets, its thick strings, its default scales and the octave of notes without one [default: guitar]"
     )]
-    tuning: Option<Tuning>,
+    instrument: Option<Instrument>,
+
+    #[arg(
+        required = false,
+        long,
+        help = "List every built-in tuning and the tunings from the config file and exit"
+    )]
+    list_tunings: bool,
 
     #[arg(
         value_enum,
@@ -165,10 +1326,106 @@ struct Args {
         required = false,
         short = 's',
         long,
-        help = "Provide a comma separated list of scales"
+        hide_possible_values = true,
+        help = "Provide a comma separated list of scales (see --list-scales)"
     )]
     scales: Option<Vec<Scale>>,
 
+    #[arg(
+        required = false,
+        short = 'u',
+        long,
+        help = "Provide a custom scale as NAME=FORMULA, where the formula is either intervals (\"1 2 b3 4 5 b6 7\") or semitones (\"0,2,3,5,7,8,11\"), the name of a scale from the config file, or the path of a Scala .scl file whose notes are rounded to the nearest semitone (or to the nearest step with --edo); can be repeated"
+    )]
+    custom_scales: Option<Vec<String>>,
+
+    #[arg(
+        value_enum,
+        value_delimiter = ',',
+        required = false,
+        short = 'g',
+        long,
+        conflicts_with_all = ["scales", "custom_scales"],
+        help = "Provide a comma separated list of scale categories to limit the random selection to, along with the scales from the config file [default: every category, or diatonic and pentatonic on bass]"
+    )]
+    categories: Option<Vec<ScaleCategory>>,
+
+    #[arg(
+        required = false,
+        short = 'l',
+        long,
+        help = "List every available scale by category and exit"
+    )]
+    list_scales: bool,
+
+    #[arg(
+        required = false,
+        short = 'x',
+        long,
+        help = "List every set of notes containing the root that passes the search filters, with its formula, Forte number and the names of the scales it matches, and exit"
+    )]
+    search_scales: bool,
+
+    #[arg(
+        value_delimiter = ',',
+        required = false,
+        long,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if (1..=NUM_NOTES).contains(&num) {
+                Ok(num)
+            } else {
+                Err(format!("Number must be between 1 and {}", NUM_NOTES))
+            }
+        },
+        conflicts_with_all = ["scales", "custom_scales", "categories"],
+        help = "Search filter: provide a comma separated list of note counts; without --search-scales, the scale of the day is picked from every scale that passes the search filters"
+    )]
+    num_notes: Option<Vec<usize>>,
+
+    #[arg(
+        required = false,
+        long,
+        conflicts_with_all = ["scales", "custom_scales", "categories"],
+        help = "Search filter: the most half steps in a row a scale may have, e.g. 1 to rule out three chromatic notes in a row"
+    )]
+    max_semitones: Option<usize>,
+
+    #[arg(
+        value_delimiter = ',',
+        required = false,
+        long,
+        value_parser = parse_interval,
+        conflicts_with_all = ["scales", "custom_scales", "categories"],
+        help = "Search filter: provide a comma separated list of degrees (\"b3,5,b7\") a scale must contain"
+    )]
+    with_degrees: Option<Vec<usize>>,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        conflicts_with_all = ["scales", "custom_scales", "categories"],
+        help = "Search filter: only keep scales that repeat within the octave (transpositional) or mirror themselves around the root (inversional)"
+    )]
+    symmetry: Option<Symmetry>,
+
+    #[arg(
+        required = false,
+        short = 'F',
+        long,
+        help = "List every root and scale containing these notes (\"C E G Bb D\"), ranked by how many notes they add, and exit"
+    )]
+    find_scales: Option<String>,
+
+    #[arg(
+        required = false,
+        short = 'S',
+        long,
+        help = "List the scales that fit a chord symbol (\"G7alt\"), ranked by how many of its tensions they contain and how few avoid notes they have, and exit"
+    )]
+    suggest_scales: Option<String>,
+
     #[arg(
         value_enum,
         value_delimiter = ',',
@@ -179,6 +1436,183 @@ struct Args {
     )]
     root_notes: Option<Vec<Accidental>>,
 
+    #[arg(
+        value_enum,
+        value_delimiter = ',',
+        required = false,
+        short = 'a',
+        long,
+        help = "Practice an arpeggio of the scale instead, picked from a comma separated list of chord types"
+    )]
+    arpeggios: Option<Vec<ChordType>>,
+
+    #[arg(
+        value_delimiter = ',',
+        required = false,
+        short = 'd',
+        long,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if num >= 1 {
+                Ok(num)
+            } else {
+                Err(String::from("Number must be >= 1"))
+            }
+        },
+        requires = "arpeggios",
+        help = "Provide a comma separated list of scale degrees to build the arpeggio on"
+    )]
+    degrees: Option<Vec<usize>>,
+
+    #[arg(
+        required = false,
+        short = 'k',
+        long,
+        help = "If enabled, the triads and seventh chords built on each degree of the scale are listed with roman numerals counted from the root (\"bVI\"); bebop scales are harmonized without their passing tone"
+    )]
+    chords: bool,
+
+    #[arg(
+        required = false,
+        short = 'v',
+        long,
+        help = "Find voicings within the starting fret window for a chord symbol (\"Am7\") or a degree of the scale as a roman numeral (\"ii7\")"
+    )]
+    voicings: Option<String>,
+
+    #[arg(
+        required = false,
+        long,
+        default_value_t = DEFAULT_MAX_STRETCH,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if num < FRET_SPAN {
+                Ok(num)
+            } else {
+                Err(format!("Number must be < {}", FRET_SPAN))
+            }
+        },
+        help = "Set the largest number of frets between the lowest and highest fretted notes of a voicing"
+    )]
+    max_stretch: usize,
+
+    #[arg(
+        required = false,
+        short = 'C',
+        long,
+        conflicts_with = "arpeggios",
+        help = "Show the tones of a chord symbol (\"Cmaj7#11\") or a degree of the scale as a roman numeral (\"V7\") on the fret board instead of the scale"
+    )]
+    chord: Option<String>,
+
+    #[arg(
+        required = false,
+        short = 'p',
+        long,
+        conflicts_with_all = ["arpeggios", "chord"],
+        help = "Practice a progression of chord symbols or roman numerals of the scale (\"ii7 V7 Imaj7\"), showing the recommended scale over each chord with its chord tones emphasized, or the mode of the scale over roman numerals"
+    )]
+    progression: Option<String>,
+
+    #[arg(
+        value_delimiter = ',',
+        required = false,
+        short = 'E',
+        long,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if (1..=13).contains(&num) {
+                Ok(num)
+            } else {
+                Err(String::from("Degree must be between 1 and 13"))
+            }
+        },
+        help = "Provide a comma separated list of degrees to emphasize while dimming the other notes, e.g. 3,7 for the guide tones or 1,5; in a progression, only the chord tones on these degrees are emphasized"
+    )]
+    emphasize: Option<Vec<usize>>,
+
+    #[arg(
+        required = false,
+        short = 'e',
+        long,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if (MIN_EDO_DIVISIONS..=MAX_EDO_DIVISIONS).contains(&num) {
+                Ok(num)
+            } else {
+                Err(format!(
+                    "Divisions must be between {} and {}",
+                    MIN_EDO_DIVISIONS, MAX_EDO_DIVISIONS
+                ))
+            }
+        },
+        conflicts_with_all = ["arpeggios", "chord", "progression", "voicings", "emphasize"],
+        help = "Show the scale in an equal division of the octave into this many steps (e.g. 19, 24 or 31) on a neck with a fret per step; arpeggios, chords, progressions, voicings and emphasized degrees are spelled on 12-TET frets and cannot be drawn on it"
+    )]
+    edo: Option<usize>,
+
+    #[arg(
+        value_enum,
+        value_delimiter = ',',
+        required = false,
+        short = 'M',
+        long,
+        requires = "edo",
+        conflicts_with_all = ["chords", "relations", "chord_fits", "analyze"],
+        help = "Provide a comma separated list of maqamat to pick from instead of the scale, written in quarter tones and mapped to the nearest steps of --edo; a maqam has no 12-TET scale to list chords, relations, chord fits or an analysis for"
+    )]
+    maqamat: Option<Vec<Maqam>>,
+
+    #[arg(
+        required = false,
+        short = 'R',
+        long,
+        help = "If enabled, the relative and parallel modes, parent scale and neighboring scales are listed"
+    )]
+    relations: bool,
+
+    #[arg(
+        required = false,
+        short = 'T',
+        long,
+        help = "If enabled, the chords on the root that fit the scale are listed, starting with those it is the first choice for"
+    )]
+    chord_fits: bool,
+
+    #[arg(
+        required = false,
+        short = 'A',
+        long,
+        help = "If enabled, the step pattern, interval content, Forte number, symmetry, tritones and avoid notes of the scale are listed"
+    )]
+    analyze: bool,
+
+    #[arg(
+        value_delimiter = ',',
+        required = false,
+        short = 'P',
+        long,
+        value_parser = parse_string_and_fret,
+        help = "Provide a comma separated list of positions as STRING:FRET, counting strings from the thinnest, to report the exact pitch and frequency of"
+    )]
+    pitch_at: Option<Vec<(usize, usize)>>,
+
+    #[arg(
+        required = false,
+        long,
+        default_value_t = DEFAULT_A4_FREQUENCY,
+        value_parser = |s: &str| {
+            let frequency = s.parse::<f64>().map_err(|_| "Not a valid frequency")?;
+            if frequency > 0.0 {
+                Ok(frequency)
+            } else {
+                Err(String::from("Frequency must be positive"))
+            }
+        },
+        help = "Set the reference frequency of A4 in Hz used to report pitches"
+    )]
+    a4: f64,
+
     #[arg(
         value_parser = value_parser!(usize),
         value_delimiter = ',',
@@ -197,6 +1631,30 @@ struct Args {
     )]
     starting_frets: Option<Vec<usize>>,
 
+    #[arg(
+        required = false,
+        long,
+        value_parser = |s: &str| {
+            let num = s.parse::<usize>().map_err(|_| "Not a valid number")?;
+            if (1..=MAX_CAPO_FRET).contains(&num) {
+                Ok(num)
+            } else {
+                Err(format!("Number must be between 1 and {}", MAX_CAPO_FRET))
+            }
+        },
+        help = "Put a capo at this fret, which becomes the nut of the fret board"
+    )]
+    capo: Option<usize>,
+
+    #[arg(
+        value_enum,
+        required = false,
+        long,
+        default_value = "relative",
+        help = "Select whether fret numbers, including those given with -f and -P, are counted from the capo or from the nut"
+    )]
+    fret_numbering: FretNumbering,
+
     #[arg(
         required = false,
         short = 'r',
@@ -212,4 +1670,21 @@ struct Args {
         help = "If enabled, the output will be in plain text without color"
     )]
     uncolored: bool,
+
+    #[arg(
+        value_enum,
+        required = false,
+        short = 'L',
+        long,
+        default_value = "notes",
+        help = "Select what to show for each note on the fret board"
+    )]
+    labels: Labels,
+
+    #[arg(
+        required = false,
+        long,
+        help = "Path to the config file [default: ~/.config/daily-scale/config.toml]"
+    )]
+    config: Option<PathBuf>,
 }
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use super::instruments::Instrument;
+use super::scales::{parse_scale_formula, CustomScale};
+use super::tunings::{parse_custom_tuning, CustomTuning};
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub scales: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tunings: BTreeMap<String, ConfigTuning>,
+}
+
+/// A tuning of the config file, either the notes of its strings for a guitar
+/// tuning or a table declaring the instrument it is played on.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum ConfigTuning {
+    Notes(String),
+    Table { notes: String, instrument: String },
+}
+
+impl Config {
+    pub fn custom_scales(&self) -> Result<Vec<CustomScale>, String> {
+        self.scales
+            .iter()
+            .map(|(name, formula)| {
+                let steps = parse_scale_formula(formula)
+                    .map_err(|err| format!("Invalid formula for scale \"{}\": {}", name, err))?;
+                Ok(CustomScale {
+                    name: name.clone(),
+                    steps,
+                })
+            })
+            .collect()
+    }
+
+    pub fn custom_tunings(&self) -> Result<Vec<CustomTuning>, String> {
+        self.tunings
+            .iter()
+            .map(|(name, config_tuning)| match config_tuning {
+                ConfigTuning::Notes(notes) => parse_custom_tuning(name, notes, Instrument::Guitar),
+                ConfigTuning::Table { notes, instrument } => {
+                    let instrument = Instrument::from_str(instrument, true).map_err(|_| {
+                        format!("Invalid instrument for tuning \"{}\": {}", name, instrument)
+                    })?;
+                    parse_custom_tuning(name, notes, instrument)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns `$XDG_CONFIG_HOME/daily-scale/config.toml`, falling back to `~/.config`.
+pub fn default_config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("daily-scale").join("config.toml"))
+}
+
+/// Loads the config file at `path`, or the default config file if it exists.
+pub fn load_config(path: Option<&Path>) -> Result<Config, String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => match default_config_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        },
+    };
+    let contents = fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read config file {}: {}", path.display(), err))?;
+    parse_config(&contents)
+        .map_err(|err| format!("Could not parse config file {}: {}", path.display(), err))
+}
+
+pub fn parse_config(contents: &str) -> Result<Config, String> {
+    toml::from_str(contents).map_err(|err| err.message().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(
+            r#"
+            [scales]
+            "Hungarian Minor" = "1 2 b3 #4 5 b6 7"
+            Hirajoshi = "0,2,3,7,8"
+
+            [tunings]
+            "Open Dm" = "D A D F A D"
+            Nashville = "E3 A3 D4 G4 B3 E4"
+            Piccolo = { notes = "E A D G", instrument = "bass" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.custom_scales(),
+            Ok(vec![
+                CustomScale {
+                    name: String::from("Hirajoshi"),
+                    steps: vec![0, 2, 3, 7, 8],
+                },
+                CustomScale {
+                    name: String::from("Hungarian Minor"),
+                    steps: vec![0, 2, 3, 6, 7, 8, 11],
+                },
+            ])
+        );
+        let tunings = config.custom_tunings().unwrap();
+        assert_eq!(tunings[0].name, "Nashville");
+        assert_eq!(tunings[1].pitches[3].to_string(), "F3");
+        assert_eq!(tunings[2].instrument, Instrument::Bass);
+        assert_eq!(tunings[2].pitches[0].to_string(), "E1");
+        assert_eq!(parse_config(""), Ok(Config::default()));
+        assert!(parse_config("[unknown]").is_err());
+    }
+
+    #[test]
+    fn test_invalid_custom_scale_and_tuning() {
+        let config = parse_config("[scales]\nbroken = \"1 2 2\"").unwrap();
+        assert!(config.custom_scales().is_err());
+        let config = parse_config("[tunings]\nbroken = \"E A X\"").unwrap();
+        assert!(config.custom_tunings().is_err());
+        let config =
+            parse_config("[tunings]\nbroken = { notes = \"E A D G\", instrument = \"lute\" }")
+                .unwrap();
+        assert!(config.custom_tunings().is_err());
+    }
+}
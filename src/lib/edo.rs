@@ -0,0 +1,334 @@
+use clap::ValueEnum;
+
+use super::notes::{note_to_string, Note, NUM_NOTES};
+use super::spelling::{spell_scale, Letter, SpelledNote, LETTERS};
+
+pub const MIN_EDO_DIVISIONS: usize = 5;
+
+pub const MAX_EDO_DIVISIONS: usize = 72;
+
+/// The divisions of the octave that maqamat are written in.
+const QUARTER_TONE_DIVISIONS: usize = 24;
+
+/// An equal division of the octave into `divisions` steps. Notes are named along
+/// the chain of fifths, so that C, G, D, A, E, B and F sit on the best fifths of
+/// the division and a sharp raises a note by a whole tone minus a diatonic semitone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Edo {
+    pub divisions: usize,
+}
+
+impl Edo {
+    pub const fn new(divisions: usize) -> Self {
+        Edo { divisions }
+    }
+
+    /// Returns the number of steps of the division closest to a perfect fifth.
+    pub fn fifth(&self) -> usize {
+        (self.divisions as f64 * 1.5f64.log2()).round() as usize
+    }
+
+    /// Returns the number of steps a sharp raises a note by, which can be 0 or less
+    /// in divisions whose fifths are far from 12-TET, e.g. 7-EDO.
+    pub fn sharp(&self) -> i32 {
+        7 * self.fifth() as i32 - 4 * self.divisions as i32
+    }
+
+    /// Returns the size of a number of steps in cents.
+    pub fn cents(&self, steps: usize) -> f64 {
+        steps as f64 * 1200.0 / self.divisions as f64
+    }
+
+    /// Returns the step of a natural note above C, from its place on the chain of
+    /// fifths (F, C, G, D, A, E, B).
+    pub fn letter_step(&self, letter: Letter) -> usize {
+        let fifths_above_c = match letter {
+            Letter::F => -1,
+            Letter::C => 0,
+            Letter::G => 1,
+            Letter::D => 2,
+            Letter::A => 3,
+            Letter::E => 4,
+            Letter::B => 5,
+        };
+        (fifths_above_c * self.fifth() as i32).rem_euclid(self.divisions as i32) as usize
+    }
+
+    /// Returns the step of a spelled note above C.
+    pub fn spelled_note_step(&self, note: SpelledNote) -> usize {
+        (self.letter_step(note.letter) as i32 + note.alteration * self.sharp())
+            .rem_euclid(self.divisions as i32) as usize
+    }
+
+    /// Returns the step of a 12-TET note above C, as it is named in `NOTES`.
+    pub fn note_step(&self, note: Note) -> usize {
+        self.spelled_note_step(note_to_string(note, false).parse().unwrap())
+    }
+
+    /// Names a step above C with the natural and accidentals closest to it.
+    pub fn step_name(&self, step: usize) -> String {
+        LETTERS
+            .iter()
+            .map(|letter| {
+                let offset = self.wrap_offset(step as i32 - self.letter_step(*letter) as i32);
+                format!("{:?}{}", letter, self.accidental_to_string(offset))
+            })
+            .min_by_key(|name| (name.chars().count(), name.contains(['b', 'd', 'v'])))
+            .unwrap()
+    }
+
+    /// Spells the notes of a scale given in steps of the division above `root`.
+    /// Seven note scales give each degree its own letter, like in 12-TET.
+    pub fn spell_steps(&self, root: SpelledNote, steps: &[usize]) -> Vec<String> {
+        let root_step = self.spelled_note_step(root);
+        let root_letter_index = LETTERS.iter().position(|&l| l == root.letter).unwrap();
+        steps
+            .iter()
+            .enumerate()
+            .map(|(degree, step)| {
+                let step = (root_step + step) % self.divisions;
+                if steps.len() != LETTERS.len() {
+                    return self.step_name(step);
+                }
+                let letter = LETTERS[(root_letter_index + degree) % LETTERS.len()];
+                let offset = self.wrap_offset(step as i32 - self.letter_step(letter) as i32);
+                format!("{:?}{}", letter, self.accidental_to_string(offset))
+            })
+            .collect()
+    }
+
+    /// Maps a 12-TET scale on `root` to the division by spelling its notes, so that
+    /// e.g. the major scale keeps its letters with 19 or 31 steps to the octave.
+    /// Notes that fall on the same step are kept once.
+    pub fn map_scale(&self, root: SpelledNote, steps: &[usize]) -> Vec<usize> {
+        let root_step = self.spelled_note_step(root);
+        let mut edo_steps = Vec::new();
+        for note in spell_scale(root, steps) {
+            let step = (self.spelled_note_step(note) + self.divisions - root_step) % self.divisions;
+            if !edo_steps.contains(&step) {
+                edo_steps.push(step);
+            }
+        }
+        edo_steps
+    }
+
+    /// Maps steps of 24-EDO to the nearest steps of the division.
+    pub fn map_quarter_tones(&self, steps: &[usize]) -> Vec<usize> {
+        let mut edo_steps = Vec::new();
+        for step in steps {
+            let edo_step = (*step as f64 * self.divisions as f64 / QUARTER_TONE_DIVISIONS as f64)
+                .round() as usize
+                % self.divisions;
+            if !edo_steps.contains(&edo_step) {
+                edo_steps.push(edo_step);
+            }
+        }
+        edo_steps
+    }
+
+    /// Returns the 12-TET step closest to a step of the division, which picks the
+    /// color of its label, so that e.g. a quarter tone flat third is colored like a
+    /// third.
+    pub fn nearest_twelve_tone_step(&self, step: usize) -> usize {
+        (step as f64 * NUM_NOTES as f64 / self.divisions as f64).round() as usize % NUM_NOTES
+    }
+
+    fn wrap_offset(&self, offset: i32) -> i32 {
+        let divisions = self.divisions as i32;
+        (offset + divisions / 2).rem_euclid(divisions) - divisions / 2
+    }
+
+    /// Writes an offset from a natural note as sharps and flats, with "+" and "d" for
+    /// a quarter-tone sharp and flat when a sharp has an even number of steps, and
+    /// "^" and "v" for any single steps left over.
+    fn accidental_to_string(&self, offset: i32) -> String {
+        let sharp = self.sharp();
+        if sharp <= 0 {
+            let arrow = if offset < 0 { "v" } else { "^" };
+            return arrow.repeat(offset.unsigned_abs() as usize);
+        }
+        let (sharp_char, quarter_char, arrow) = if offset < 0 {
+            ('b', 'd', 'v')
+        } else {
+            ('#', '+', '^')
+        };
+        let num_sharps = offset.abs() / sharp;
+        let mut rest = offset.abs() % sharp;
+        let mut accidental = sharp_char.to_string().repeat(num_sharps as usize);
+        if sharp % 2 == 0 && rest == sharp / 2 {
+            accidental.push(quarter_char);
+            rest = 0;
+        }
+        accidental.push_str(&arrow.to_string().repeat(rest as usize));
+        accidental
+    }
+}
+
+/// Arabic maqamat, written in quarter tones from their tonic.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Maqam {
+    Rast,
+    Bayati,
+    Saba,
+    Sikah,
+    Huzam,
+    Hijaz,
+    Nahawand,
+    Kurd,
+    Ajam,
+}
+
+pub const fn get_quarter_tone_steps_by_maqam(maqam: Maqam) -> &'static [usize] {
+    match maqam {
+        Maqam::Rast => &[0, 4, 7, 10, 14, 18, 21],
+        Maqam::Bayati => &[0, 3, 6, 10, 14, 16, 20],
+        Maqam::Saba => &[0, 3, 6, 8, 14, 16, 20],
+        Maqam::Sikah => &[0, 3, 7, 10, 14, 17, 21],
+        Maqam::Huzam => &[0, 3, 7, 9, 15, 17, 21],
+        Maqam::Hijaz => &[0, 2, 8, 10, 14, 16, 20],
+        Maqam::Nahawand => &[0, 4, 6, 10, 14, 16, 22],
+        Maqam::Kurd => &[0, 2, 6, 10, 14, 16, 20],
+        Maqam::Ajam => &[0, 4, 8, 10, 14, 18, 22],
+    }
+}
+
+pub const fn maqam_to_string(maqam: Maqam) -> &'static str {
+    match maqam {
+        Maqam::Rast => "Rast",
+        Maqam::Bayati => "Bayati",
+        Maqam::Saba => "Saba",
+        Maqam::Sikah => "Sikah",
+        Maqam::Huzam => "Huzam",
+        Maqam::Hijaz => "Hijaz",
+        Maqam::Nahawand => "Nahawand",
+        Maqam::Kurd => "Kurd",
+        Maqam::Ajam => "Ajam",
+    }
+}
+
+/// A scale in an equal division of the octave, with its steps above the root and
+/// the names of its notes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdoScale {
+    pub edo: Edo,
+    pub name: String,
+    pub root_step: usize,
+    pub steps: Vec<usize>,
+    pub names: Vec<String>,
+    /// The size of each note above the root in cents, which is exact for scales
+    /// given in cents rather than in steps.
+    pub cents: Vec<f64>,
+}
+
+impl EdoScale {
+    pub fn new(edo: Edo, name: &str, root: SpelledNote, steps: Vec<usize>) -> Self {
+        EdoScale {
+            edo,
+            name: String::from(name),
+            root_step: edo.spelled_note_step(root),
+            names: edo.spell_steps(root, &steps),
+            cents: steps.iter().map(|step| edo.cents(*step)).collect(),
+            steps,
+        }
+    }
+
+    /// Puts each note of a scale given in cents on the nearest step of the division.
+    /// Notes that fall on the same step are kept once.
+    pub fn from_cents(edo: Edo, name: &str, root: SpelledNote, note_cents: &[f64]) -> Self {
+        let mut steps = Vec::new();
+        let mut cents = Vec::new();
+        for note_cent in note_cents {
+            let step = (note_cent / edo.cents(1)).round() as usize % edo.divisions;
+            if !steps.contains(&step) {
+                steps.push(step);
+                cents.push(*note_cent);
+            }
+        }
+        EdoScale {
+            cents,
+            ..EdoScale::new(edo, name, root, steps)
+        }
+    }
+
+    /// Returns the position in `steps` of a step of the division above C.
+    pub fn position(&self, step: usize) -> Option<usize> {
+        let relative_step = (step + self.edo.divisions - self.root_step) % self.edo.divisions;
+        self.steps.iter().position(|s| *s == relative_step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edo_naming() {
+        let edo = Edo::new(12);
+        assert_eq!(edo.sharp(), 1);
+        assert_eq!(
+            (0..12)
+                .map(|step| edo.step_name(step))
+                .collect::<Vec<String>>(),
+            vec!["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"]
+        );
+
+        let edo = Edo::new(24);
+        assert_eq!(edo.sharp(), 2);
+        assert_eq!(edo.step_name(1), "C+");
+        assert_eq!(edo.step_name(3), "Dd");
+        assert_eq!(edo.step_name(7), "Ed");
+
+        let edo = Edo::new(19);
+        assert_eq!(edo.fifth(), 11);
+        assert_eq!(edo.step_name(1), "C#");
+        assert_eq!(edo.step_name(2), "Db");
+        assert_eq!(edo.note_step(Note::E), 6);
+
+        let edo = Edo::new(31);
+        assert_eq!(edo.step_name(1), "C+");
+        assert_eq!(edo.step_name(2), "C#");
+        assert_eq!(edo.cents(18).round(), 697.0);
+    }
+
+    #[test]
+    fn test_edo_scales() {
+        let major = [0, 2, 4, 5, 7, 9, 11];
+        let c = "C".parse().unwrap();
+        assert_eq!(
+            Edo::new(19).map_scale(c, &major),
+            vec![0, 3, 6, 8, 11, 14, 17]
+        );
+        assert_eq!(
+            Edo::new(24).map_scale(c, &major),
+            vec![0, 4, 8, 10, 14, 18, 22]
+        );
+
+        let d = "D".parse().unwrap();
+        let edo = Edo::new(24);
+        let saba = edo.map_quarter_tones(get_quarter_tone_steps_by_maqam(Maqam::Saba));
+        assert_eq!(
+            edo.spell_steps(d, &saba),
+            vec!["D", "Ed", "F", "Gb", "A", "Bb", "C"]
+        );
+        let sikah = EdoScale::new(
+            edo,
+            "Sikah",
+            "E".parse().unwrap(),
+            edo.map_quarter_tones(get_quarter_tone_steps_by_maqam(Maqam::Sikah)),
+        );
+        assert_eq!(sikah.names, vec!["E", "F+", "G+", "A", "B", "C+", "D+"]);
+        assert_eq!(sikah.position(18), Some(3));
+        assert_eq!(sikah.position(19), None);
+        assert_eq!(edo.nearest_twelve_tone_step(saba[1]), 2);
+        assert_eq!(Edo::new(19).nearest_twelve_tone_step(6), 4);
+        assert_eq!(Edo::new(19).nearest_twelve_tone_step(18), 11);
+
+        let just_triad = EdoScale::from_cents(Edo::new(31), "Just", c, &[0.0, 386.314, 701.955]);
+        assert_eq!(just_triad.steps, vec![0, 10, 18]);
+        assert_eq!(just_triad.cents[1], 386.314);
+        assert_eq!(
+            Edo::new(48).map_quarter_tones(get_quarter_tone_steps_by_maqam(Maqam::Rast)),
+            vec![0, 8, 14, 20, 28, 36, 42]
+        );
+    }
+}
@@ -1,49 +1,334 @@
-use super::cli::{format_with_color, Format};
-use super::notes::{note_to_string, Note, NOTES, NUM_NOTES};
-use super::tunings::{get_notes_by_tuning, Tuning};
+use super::chords::Chord;
+use super::cli::{format_with_color, format_with_emphasis, Format, FretNumbering, Labels};
+use super::edo::{Edo, EdoScale};
+use super::pitch_classes::PitchClassSet;
+use super::pitches::Pitch;
+use super::spelling::{get_degree, Degree, SpelledNote};
+use super::tunings::TuningChoice;
 
 pub const NUM_FRETS: usize = 24;
 
 pub const FRET_SPAN: usize = 5;
 
+/// The highest fret a capo can be put at, which leaves room for a whole fret span
+/// on every neck.
+pub const MAX_CAPO_FRET: usize = 12;
+
+/// A capo clamped behind a fret, which becomes the nut of the fret board. Frets are
+/// counted from the capo, and shown either from the capo or from the nut.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Capo {
+    pub fret: usize,
+    pub numbering: FretNumbering,
+}
+
+impl Capo {
+    /// Returns the number shown for a fret counted from the capo.
+    pub const fn fret_number(&self, fret: usize) -> usize {
+        match self.numbering {
+            FretNumbering::Relative => fret,
+            FretNumbering::Absolute => fret + self.fret,
+        }
+    }
+
+    /// Returns the fret counted from the capo that is shown as `number`, or `None`
+    /// if it is behind the capo.
+    pub const fn fret_from_number(&self, number: usize) -> Option<usize> {
+        match self.numbering {
+            FretNumbering::Relative => Some(number),
+            FretNumbering::Absolute => number.checked_sub(self.fret),
+        }
+    }
+
+    /// Returns the capo on a neck of an equal division of the octave, behind the fret
+    /// at the same place as its 12-TET fret or the one just below it.
+    pub fn in_edo(&self, edo: Edo) -> Capo {
+        Capo {
+            fret: get_edo_fret(edo, self.fret),
+            numbering: self.numbering,
+        }
+    }
+}
+
+/// The notes shown on the fret board, with the name and degree of each of them in
+/// the order of the steps of `pitch_classes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FretBoardNotes {
+    pub pitch_classes: PitchClassSet,
+    pub names: Vec<SpelledNote>,
+    pub degrees: Vec<Degree>,
+    /// Whether each note stands out on the fret board; the others are dimmed.
+    pub emphasized: Vec<bool>,
+}
+
+impl FretBoardNotes {
+    /// Names the degree of each note after the distance between its letter and the
+    /// letter of the root.
+    pub fn new(pitch_classes: PitchClassSet, names: Vec<SpelledNote>) -> Self {
+        let root = pitch_classes
+            .position(pitch_classes.root())
+            .map(|position| names[position]);
+        let degrees = names
+            .iter()
+            .zip(pitch_classes.steps())
+            .map(|(name, step)| get_degree(root.unwrap_or(*name), *name, step))
+            .collect();
+        let emphasized = vec![true; names.len()];
+        FretBoardNotes {
+            pitch_classes,
+            names,
+            degrees,
+            emphasized,
+        }
+    }
+
+    /// Keeps the degrees of the chord, e.g. 9 and #11 rather than 2 and #4.
+    pub fn from_chord(chord: &Chord) -> Self {
+        let mut tones = chord
+            .intervals()
+            .into_iter()
+            .zip(chord.note_names())
+            .zip(chord.degrees.iter().copied())
+            .collect::<Vec<((usize, SpelledNote), Degree)>>();
+        tones.sort_by_key(|((interval, _), _)| *interval);
+        tones.dedup_by_key(|((interval, _), _)| *interval);
+        let intervals = tones
+            .iter()
+            .map(|((interval, _), _)| *interval)
+            .collect::<Vec<usize>>();
+        FretBoardNotes {
+            pitch_classes: PitchClassSet::from_steps(chord.root.note(), &intervals),
+            names: tones.iter().map(|((_, name), _)| *name).collect(),
+            degrees: tones.iter().map(|(_, degree)| *degree).collect(),
+            emphasized: vec![true; tones.len()],
+        }
+    }
+
+    /// Emphasizes only the notes on `steps` above the root, e.g. the chord tones
+    /// within a scale.
+    pub fn emphasize_steps(mut self, steps: &[usize]) -> Self {
+        self.emphasized = self
+            .pitch_classes
+            .steps()
+            .iter()
+            .map(|step| steps.contains(step))
+            .collect();
+        self
+    }
+
+    /// Keeps the emphasis only on the notes whose degree has one of `numbers`,
+    /// whatever its alteration, e.g. 3 and 7 for the guide tones. Numbers above 7
+    /// also match the degree an octave lower, so 9 matches 2. Degree 0 does not
+    /// exist and matches no note.
+    pub fn emphasize_degrees(mut self, numbers: &[usize]) -> Self {
+        let degree_index = |number: usize| number.checked_sub(1).map(|index| index % 7);
+        for (emphasized, degree) in self.emphasized.iter_mut().zip(&self.degrees) {
+            *emphasized &= numbers.iter().any(|number| {
+                degree_index(*number)
+                    .is_some_and(|index| Some(index) == degree_index(degree.number))
+            });
+        }
+        self
+    }
+
+    /// Returns whether some of the notes are dimmed.
+    pub fn has_emphasis(&self) -> bool {
+        self.emphasized.contains(&false)
+    }
+}
+
 pub fn build_fret_board(
-    tuning: Tuning,
+    tuning: &TuningChoice,
+    capo: &Capo,
     starting_fret: usize,
-    notes_in_scale: &[(Note, usize)],
+    notes: &FretBoardNotes,
     format: &Format,
 ) -> Vec<String> {
-    let mut fret_board = Vec::new();
-    let notes_in_tuning = get_notes_by_tuning(tuning);
-    for (string_counter, string) in notes_in_tuning.iter().enumerate() {
-        let string_char = if string_counter < (notes_in_tuning.len() - NUM_THICK_STRINGS) {
-            '='
-        } else {
-            '-'
-        };
-        let fret_board_string =
-            build_fret_board_string(starting_fret, notes_in_scale, *string, string_char, format);
-        fret_board.insert(0, fret_board_string);
+    let mut note_labels = get_note_labels(notes, format.labels);
+    if notes.has_emphasis() && !format.colored {
+        for (label, emphasized) in note_labels.iter_mut().zip(&notes.emphasized) {
+            if *emphasized {
+                *label = format!("[{}]", label);
+            }
+        }
     }
-    let fret_num_string = build_fret_num_string(starting_fret);
-    fret_board.push(fret_num_string);
-    fret_board
+    let label_width = get_label_width(&note_labels);
+    render_fret_board(
+        &get_fret_board_strings(tuning, TWELVE_TET, capo.fret),
+        &get_window_frets(starting_fret, capo),
+        TWELVE_TET.divisions,
+        label_width,
+        get_note_marker(notes, &note_labels),
+        format,
+    )
 }
 
-const NUM_THICK_STRINGS: usize = 3;
+/// Renders a scale of an equal division of the octave on a neck with a fret per step.
+/// The starting fret and the capo are 12-TET frets, so that the window covers the
+/// same hand position as the 12-TET fret board.
+pub fn build_edo_fret_board(
+    tuning: &TuningChoice,
+    capo: &Capo,
+    starting_fret: usize,
+    scale: &EdoScale,
+    format: &Format,
+) -> Vec<String> {
+    let edo = scale.edo;
+    let note_labels = get_edo_note_labels(scale, format.labels);
+    let label_width = get_label_width(&note_labels);
+    let edo_capo = capo.in_edo(edo);
+    let first_fret = get_edo_starting_fret(edo, capo, starting_fret);
+    let num_frets = (FRET_SPAN * edo.divisions).div_ceil(NUM_FRETS_PER_OCTAVE);
+    let last_fret =
+        (first_fret + num_frets).min(get_edo_fret(edo, tuning.num_frets()) + 1 - edo_capo.fret);
+    let frets = (first_fret..last_fret)
+        .map(|fret| WindowFret {
+            fret,
+            length: get_edo_fret_length(edo, edo_capo.fret + fret, label_width),
+            number: edo_capo.fret_number(fret),
+        })
+        .collect::<Vec<WindowFret>>();
+    let mark_step = |step: usize| {
+        scale.position(step).map(|position| NoteMark {
+            label: &note_labels[position],
+            color_step: edo.nearest_twelve_tone_step(scale.steps[position]),
+            emphasized: None,
+        })
+    };
+    render_fret_board(
+        &get_fret_board_strings(tuning, edo, capo.fret),
+        &frets,
+        edo.divisions,
+        label_width,
+        mark_step,
+        format,
+    )
+}
+
+/// Returns the fret of an equal division of the octave at the same place on the neck
+/// as a 12-TET fret, or the one just below it.
+pub fn get_edo_fret(edo: Edo, fret: usize) -> usize {
+    fret * edo.divisions / NUM_FRETS_PER_OCTAVE
+}
+
+/// Returns the first fret of the window of an equal division of the octave, counting
+/// from the capo, for a 12-TET starting fret counted from the capo.
+pub fn get_edo_starting_fret(edo: Edo, capo: &Capo, starting_fret: usize) -> usize {
+    get_edo_fret(edo, capo.fret + starting_fret) - get_edo_fret(edo, capo.fret)
+}
+
+/// Returns the pitch played at a fret of a string of the tuning, counting strings
+/// from the thickest starting at 0, or `None` if the string does not reach the fret.
+pub fn get_pitch_at(tuning: &TuningChoice, string: usize, fret: usize) -> Option<Pitch> {
+    let first_fret = *tuning.first_frets().get(string)?;
+    if fret > tuning.num_frets() || fret < first_fret {
+        return None;
+    }
+    tuning
+        .pitches()
+        .get(string)
+        .map(|pitch| pitch.transpose((fret - first_fret) as i32))
+}
+
+const NUM_FRETS_PER_OCTAVE: usize = 12;
+
+/// The division of the octave of a fret board with a fret per semitone.
+const TWELVE_TET: Edo = Edo::new(NUM_FRETS_PER_OCTAVE);
+
+/// Scales the distance between two frets on the neck to characters, so that the
+/// first fret of 12-TET is 10 characters long like in `FRET_LENGTH`.
+const FRET_LENGTH_SCALE: f64 = 178.0;
 
 const FRET_LENGTH: [usize; 25] = [
     0, 10, 10, 9, 9, 9, 8, 8, 8, 8, 7, 7, 7, 7, 7, 6, 6, 6, 6, 6, 6, 5, 5, 5, 5,
 ];
 
-fn format_note(note: Note, step: usize, string_char: char, format: &Format) -> String {
-    let Format { flat, colored } = *format;
-    let note_string = note_to_string(note, flat);
-    let colored_note = format_with_color(note_string, step, colored);
-    if note_string.len() == 1 {
-        format!("{}{}", colored_note, string_char)
-    } else {
-        colored_note
+/// Returns the length of a fret of an equal division of the octave in characters,
+/// following the distance between the frets on the neck but never shorter than a
+/// label.
+fn get_edo_fret_length(edo: Edo, fret: usize, label_width: usize) -> usize {
+    if fret == 0 {
+        return 0;
     }
+    let position = |fret: usize| 1.0 - 2f64.powf(-(fret as f64) / edo.divisions as f64);
+    let length = (FRET_LENGTH_SCALE * (position(fret) - position(fret - 1))).round() as usize;
+    length.max(label_width + 2)
+}
+
+fn get_edo_note_labels(scale: &EdoScale, labels: Labels) -> Vec<String> {
+    scale
+        .names
+        .iter()
+        .zip(&scale.steps)
+        .enumerate()
+        .map(|(degree, (name, step))| match labels {
+            Labels::Notes => name.clone(),
+            Labels::Degrees if *step == 0 => String::from("R"),
+            Labels::Degrees => (degree + 1).to_string(),
+            Labels::Intervals => format!("{:.0}", scale.cents[degree]),
+            Labels::Semitones => step.to_string(),
+        })
+        .collect()
+}
+
+fn push_fret_chars(fret_board_string: &mut String, fret_char: char, length: usize) {
+    for _ in 0..length {
+        fret_board_string.push(fret_char);
+    }
+}
+
+fn get_note_labels(notes: &FretBoardNotes, labels: Labels) -> Vec<String> {
+    notes
+        .names
+        .iter()
+        .zip(&notes.degrees)
+        .zip(notes.pitch_classes.steps())
+        .map(|((name, degree), step)| match labels {
+            Labels::Notes => name.to_string(),
+            Labels::Degrees if step == 0 => String::from("R"),
+            Labels::Degrees => degree.to_string(),
+            Labels::Intervals => degree.interval_name(),
+            Labels::Semitones => step.to_string(),
+        })
+        .collect()
+}
+
+/// Every label is padded to the same width so that they line up across strings;
+/// this is 2 unless a label is longer, e.g. a double sharp or "bb7".
+fn get_label_width(note_labels: &[String]) -> usize {
+    note_labels
+        .iter()
+        .map(|label| label.len())
+        .fold(2, usize::max)
+}
+
+fn format_note(
+    label: &str,
+    step: usize,
+    emphasized: Option<bool>,
+    string_char: char,
+    label_width: usize,
+    format: &Format,
+) -> String {
+    let Format { colored, .. } = *format;
+    // Without color, emphasized labels already come in brackets.
+    let colored_note = match emphasized {
+        Some(emphasized) if colored => format_with_emphasis(label, step, emphasized, colored),
+        _ => format_with_color(label, step, colored),
+    };
+    let padding = label_width.saturating_sub(label.len());
+    format!(
+        "{}{}",
+        colored_note,
+        string_char.to_string().repeat(padding)
+    )
+}
+
+/// Splits the space around a label of `label_width` within a fret, leaning left.
+fn split_fret_length(fret_length: usize, label_width: usize) -> (usize, usize) {
+    let first_half_fret_length = (fret_length - label_width).div_ceil(2);
+    let second_half_fret_length = fret_length - label_width - first_half_fret_length;
+    (first_half_fret_length, second_half_fret_length)
 }
 
 fn format_fret_num(fret_num: usize) -> String {
@@ -55,82 +340,194 @@ fn format_fret_num(fret_num: usize) -> String {
     }
 }
 
-fn build_fret_board_string(
-    starting_fret: usize,
-    notes_in_scale: &[(Note, usize)],
-    string: Note,
+/// A string as it is drawn on the fret board.
+struct FretBoardString {
+    /// The step above C played at `first_fret`, in the division of the fret board.
+    step: usize,
+    /// The first fret the string can be played at, counting from the capo.
+    first_fret: usize,
     string_char: char,
+}
+
+/// Returns the strings of the tuning from the thickest as they sound on a neck of
+/// `edo` with a capo behind the 12-TET fret `capo_fret`, or without a capo if it is
+/// 0. A string that starts past the capo, like the fifth string of a banjo, is left
+/// as it is.
+fn get_fret_board_strings(
+    tuning: &TuningChoice,
+    edo: Edo,
+    capo_fret: usize,
+) -> Vec<FretBoardString> {
+    let capo_fret = get_edo_fret(edo, capo_fret);
+    tuning
+        .pitches()
+        .iter()
+        .zip(tuning.first_frets())
+        .enumerate()
+        .map(|(string, (pitch, first_fret))| {
+            let first_fret = get_edo_fret(edo, first_fret);
+            FretBoardString {
+                step: (edo.note_step(pitch.note) + capo_fret.saturating_sub(first_fret))
+                    % edo.divisions,
+                first_fret: first_fret.saturating_sub(capo_fret),
+                string_char: if tuning.is_thick_string(string) {
+                    '='
+                } else {
+                    '-'
+                },
+            }
+        })
+        .collect()
+}
+
+/// A fret in the window of a fret board, counting from the capo. Fret 0 is the nut,
+/// or the capo if there is one.
+struct WindowFret {
+    fret: usize,
+    /// The length of the fret in characters, following its place on the neck.
+    length: usize,
+    /// The number shown under the fret.
+    number: usize,
+}
+
+/// Returns the 12-TET frets of the window starting at `starting_fret`.
+fn get_window_frets(starting_fret: usize, capo: &Capo) -> Vec<WindowFret> {
+    (starting_fret..starting_fret + FRET_SPAN)
+        .map(|fret| WindowFret {
+            fret,
+            length: FRET_LENGTH[capo.fret + fret],
+            number: capo.fret_number(fret),
+        })
+        .collect()
+}
+
+/// How a note of the scale is drawn on the fret board.
+struct NoteMark<'a> {
+    label: &'a str,
+    /// The 12-TET step above the root that picks the color of the label.
+    color_step: usize,
+    emphasized: Option<bool>,
+}
+
+/// Returns how to draw a note a number of 12-TET steps above C, if it is one of
+/// `notes`.
+fn get_note_marker<'a>(
+    notes: &'a FretBoardNotes,
+    note_labels: &'a [String],
+) -> impl Fn(usize) -> Option<NoteMark<'a>> {
+    let root_step = TWELVE_TET.note_step(notes.pitch_classes.root());
+    let steps = notes.pitch_classes.steps();
+    move |step| {
+        let step = (step + NUM_FRETS_PER_OCTAVE - root_step) % NUM_FRETS_PER_OCTAVE;
+        let position = steps.iter().position(|s| *s == step)?;
+        Some(NoteMark {
+            label: &note_labels[position],
+            color_step: step,
+            emphasized: notes.has_emphasis().then_some(notes.emphasized[position]),
+        })
+    }
+}
+
+/// Draws the strings from the thinnest and the fret numbers over a window of frets,
+/// on a neck of an equal division of the octave into `divisions` steps.
+/// `mark_step` tells how to draw a note a number of steps above C, or returns
+/// `None` if it is not shown.
+fn render_fret_board<'a>(
+    strings: &[FretBoardString],
+    frets: &[WindowFret],
+    divisions: usize,
+    label_width: usize,
+    mark_step: impl Fn(usize) -> Option<NoteMark<'a>>,
+    format: &Format,
+) -> Vec<String> {
+    let mut fret_board = strings
+        .iter()
+        .rev()
+        .map(|string| {
+            build_fret_board_string(string, frets, divisions, label_width, &mark_step, format)
+        })
+        .collect::<Vec<String>>();
+    fret_board.push(build_fret_num_string(frets, label_width));
+    fret_board
+}
+
+/// Draws a string over the frets of the window. Frets below the first fret of the
+/// string are left out.
+fn build_fret_board_string<'a>(
+    string: &FretBoardString,
+    frets: &[WindowFret],
+    divisions: usize,
+    label_width: usize,
+    mark_step: &impl Fn(usize) -> Option<NoteMark<'a>>,
     format: &Format,
 ) -> String {
+    let FretBoardString {
+        step: first_step,
+        first_fret,
+        string_char,
+    } = *string;
     let mut fret_board_string = String::new();
-    let empty_string_note_index = NOTES.iter().position(|&note| note == string).unwrap();
-    let notes_in_string = (0..=NUM_NOTES)
-        .map(|fret| {
-            let note_index = (empty_string_note_index + fret) % NUM_NOTES;
-            NOTES[note_index]
-        })
-        .collect::<Vec<Note>>();
-    for fret in starting_fret..(starting_fret + FRET_SPAN) {
-        let note = notes_in_string[fret % NUM_NOTES];
-        if fret == 0 {
-            if let Some((_, step)) = notes_in_scale
-                .iter()
-                .find(|(note_in_scale, _)| *note_in_scale == note)
-            {
-                fret_board_string.push_str(format_note(note, *step, string_char, format).as_str());
-            } else {
-                fret_board_string.push(string_char);
-                fret_board_string.push(string_char);
-            }
-        } else {
+    for WindowFret { fret, length, .. } in frets {
+        let fret = *fret;
+        let length = if fret == 0 { label_width } else { *length };
+        if fret != 0 {
             fret_board_string.push('|');
-            let fret_length = FRET_LENGTH[fret];
-            if let Some((_, step)) = notes_in_scale
-                .iter()
-                .find(|(note_in_scale, _)| *note_in_scale == note)
-            {
-                let fret_length_odd = fret_length % 2 != 0;
-                let first_half_fret_length = fret_length / 2 - if fret_length_odd { 0 } else { 1 };
-                let second_half_fret_length = fret_length / 2 - 1;
-                for _ in 0..first_half_fret_length {
-                    fret_board_string.push(string_char);
-                }
-                fret_board_string.push_str(format_note(note, *step, string_char, format).as_str());
-                for _ in 0..second_half_fret_length {
-                    fret_board_string.push(string_char);
-                }
+        }
+        if fret < first_fret {
+            push_fret_chars(&mut fret_board_string, ' ', length);
+            continue;
+        }
+        let step = (first_step + fret - first_fret) % divisions;
+        if let Some(NoteMark {
+            label,
+            color_step,
+            emphasized,
+        }) = mark_step(step)
+        {
+            let (first_half_fret_length, second_half_fret_length) = if fret == 0 {
+                (0, 0)
             } else {
-                for _ in 0..fret_length {
-                    fret_board_string.push(string_char);
-                }
-            }
+                split_fret_length(length, label_width)
+            };
+            push_fret_chars(&mut fret_board_string, string_char, first_half_fret_length);
+            fret_board_string.push_str(
+                format_note(
+                    label,
+                    color_step,
+                    emphasized,
+                    string_char,
+                    label_width,
+                    format,
+                )
+                .as_str(),
+            );
+            push_fret_chars(&mut fret_board_string, string_char, second_half_fret_length);
+        } else {
+            push_fret_chars(&mut fret_board_string, string_char, length);
         }
     }
     fret_board_string.push('|');
     fret_board_string
 }
 
-fn build_fret_num_string(starting_fret: usize) -> String {
+fn build_fret_num_string(frets: &[WindowFret], label_width: usize) -> String {
     let mut fret_num_string = String::new();
-    (starting_fret..(starting_fret + FRET_SPAN)).for_each(|fret| {
-        if fret == 0 {
-            fret_num_string.push(' ');
-            fret_num_string.push(' ');
-        } else {
-            fret_num_string.push('|');
-            let fret_length = FRET_LENGTH[fret];
-            let fret_length_odd = fret_length % 2 != 0;
-            let first_half_fret_length = fret_length / 2 - if fret_length_odd { 0 } else { 1 };
-            let second_half_fret_length = fret_length / 2 - 1;
-            for _ in 0..first_half_fret_length {
-                fret_num_string.push(' ');
-            }
-            fret_num_string.push_str(format_fret_num(fret).as_str());
-            for _ in 0..second_half_fret_length {
-                fret_num_string.push(' ');
-            }
+    for WindowFret {
+        fret,
+        length,
+        number,
+    } in frets
+    {
+        if *fret == 0 {
+            push_fret_chars(&mut fret_num_string, ' ', label_width);
+            continue;
         }
-    });
+        fret_num_string.push('|');
+        let (first_half_fret_length, second_half_fret_length) = split_fret_length(*length, 2);
+        push_fret_chars(&mut fret_num_string, ' ', first_half_fret_length);
+        fret_num_string.push_str(format_fret_num(*number).as_str());
+        push_fret_chars(&mut fret_num_string, ' ', second_half_fret_length);
+    }
     fret_num_string.push('|');
     fret_num_string
 }
@@ -138,44 +535,72 @@ fn build_fret_num_string(starting_fret: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lib::edo::{get_quarter_tone_steps_by_maqam, Maqam};
+    use crate::lib::notes::Note;
+    use crate::lib::spelling::Letter;
+    use crate::lib::tunings::Tuning;
+
+    fn spelled(names: &[&str]) -> Vec<SpelledNote> {
+        names.iter().map(|name| name.parse().unwrap()).collect()
+    }
 
     #[test]
     fn test_format_note() {
         assert_eq!(
             format_note(
-                Note::A,
+                "A",
                 0,
+                None,
                 '-',
+                2,
                 &Format {
-                    flat: true,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes
                 }
             ),
             "A-"
         );
         assert_eq!(
             format_note(
-                Note::CSharp,
+                "C#",
                 0,
+                None,
                 '=',
+                2,
                 &Format {
-                    flat: false,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes
                 },
             ),
             "C#"
         );
         assert_eq!(
             format_note(
-                Note::GSharp,
+                "Ab",
                 0,
+                None,
                 '-',
+                3,
                 &Format {
-                    flat: true,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes
                 },
             ),
-            "Ab"
+            "Ab-"
+        );
+        assert_eq!(
+            format_note(
+                "Bbb",
+                0,
+                None,
+                '-',
+                3,
+                &Format {
+                    colored: false,
+                    labels: Labels::Notes
+                },
+            ),
+            "Bbb"
         );
     }
 
@@ -187,77 +612,149 @@ mod tests {
 
     #[test]
     fn test_build_fret_board_string() {
-        assert_eq!(
+        let build = |starting_fret, notes: FretBoardNotes, string: FretBoardString| {
+            let note_labels = get_note_labels(&notes, Labels::Notes);
+            let mark_step = get_note_marker(&notes, &note_labels);
             build_fret_board_string(
-                5,
-                &[(Note::A, 0), (Note::B, 2), (Note::C, 3)],
-                Note::E,
-                '=',
+                &string,
+                &get_window_frets(starting_fret, &Capo::default()),
+                NUM_FRETS_PER_OCTAVE,
+                2,
+                &mark_step,
                 &Format {
-                    flat: false,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes,
+                },
+            )
+        };
+        assert_eq!(
+            build(
+                5,
+                FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::A, &[0, 2, 3]),
+                    spelled(&["A", "B", "C"])
+                ),
+                FretBoardString {
+                    step: 4,
+                    first_fret: 0,
+                    string_char: '=',
                 },
             ),
             "|====A====|========|===B====|===C====|========|"
         );
         assert_eq!(
-            build_fret_board_string(
+            build(
                 12,
-                &[(Note::DSharp, 1), (Note::E, 2), (Note::FSharp, 4)],
-                Note::D,
-                '=',
-                &Format {
-                    flat: false,
-                    colored: false
+                FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::D, &[1, 2, 4]),
+                    spelled(&["D#", "E", "F#"])
+                ),
+                FretBoardString {
+                    step: 2,
+                    first_fret: 0,
+                    string_char: '=',
                 },
             ),
             "|=======|===D#==|===E===|======|==F#==|"
         );
         assert_eq!(
-            build_fret_board_string(
+            build(
                 0,
-                &[(Note::B, 0), (Note::CSharp, 2), (Note::DSharp, 4)],
-                Note::B,
-                '-',
-                &Format {
-                    flat: true,
-                    colored: false
+                FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::B, &[0, 2, 4]),
+                    spelled(&["B", "Db", "Eb"])
+                ),
+                FretBoardString {
+                    step: 11,
+                    first_fret: 0,
+                    string_char: '-',
                 },
             ),
             "B-|----------|----Db----|---------|----Eb---|"
         );
+        assert_eq!(
+            build(
+                3,
+                FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::G, &[0, 2, 4]),
+                    spelled(&["G", "A", "B"])
+                ),
+                FretBoardString {
+                    step: 7,
+                    first_fret: 5,
+                    string_char: '-',
+                },
+            ),
+            "|         |         |----G----|--------|---A----|"
+        );
+    }
+
+    #[test]
+    fn test_get_pitch_at() {
+        assert_eq!(
+            get_pitch_at(&TuningChoice::BuiltIn(Tuning::StandardE6), 0, 5),
+            Some(Pitch::new(Note::A, 2))
+        );
+        assert_eq!(
+            get_pitch_at(&TuningChoice::BuiltIn(Tuning::StandardE6), 5, 12),
+            Some(Pitch::new(Note::E, 5))
+        );
+        assert_eq!(
+            get_pitch_at(&TuningChoice::BuiltIn(Tuning::StandardB7), 0, 1),
+            Some(Pitch::new(Note::C, 2))
+        );
+        assert_eq!(
+            get_pitch_at(&TuningChoice::BuiltIn(Tuning::StandardE6), 6, 0),
+            None
+        );
+        assert_eq!(
+            get_pitch_at(&TuningChoice::BuiltIn(Tuning::StandardE6), 0, NUM_FRETS + 1),
+            None
+        );
+        let banjo = TuningChoice::BuiltIn(Tuning::BanjoOpenG5);
+        assert_eq!(get_pitch_at(&banjo, 0, 7), Some(Pitch::new(Note::A, 4)));
+        assert_eq!(get_pitch_at(&banjo, 0, 4), None);
+        assert_eq!(get_pitch_at(&banjo, 1, 23), None);
     }
 
     #[test]
     fn test_build_fret_num_string() {
         assert_eq!(
-            build_fret_num_string(0),
+            build_fret_num_string(&get_window_frets(0, &Capo::default()), 2),
             "  |    1     |    2     |    3    |    4    |"
         );
         assert_eq!(
-            build_fret_num_string(12),
+            build_fret_num_string(&get_window_frets(12, &Capo::default()), 2),
             "|   12  |   13  |   14  |  15  |  16  |"
         );
+        assert_eq!(
+            build_fret_num_string(&get_window_frets(0, &Capo::default()), 3),
+            "   |    1     |    2     |    3    |    4    |"
+        );
     }
 
     #[test]
     fn test_build_fret_board() {
         assert_eq!(
             build_fret_board(
-                Tuning::OpenG6,
+                &TuningChoice::BuiltIn(Tuning::OpenG6),
+                &Capo::default(),
                 0,
-                &[
-                    (Note::A, 0),
-                    (Note::B, 2),
-                    (Note::C, 3),
-                    (Note::D, 5),
-                    (Note::E, 7),
-                    (Note::F, 8),
-                    (Note::GSharp, 11),
-                ],
+                &FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::A, &[0, 2, 3, 5, 7, 8, 11]),
+                    vec![
+                        SpelledNote::new(Letter::A, 0),
+                        SpelledNote::new(Letter::B, 0),
+                        SpelledNote::new(Letter::C, 0),
+                        SpelledNote::new(Letter::D, 0),
+                        SpelledNote::new(Letter::E, 0),
+                        SpelledNote::new(Letter::F, 0),
+                        SpelledNote::new(Letter::G, 1)
+                    ]
+                ),
                 &Format {
-                    flat: false,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes
                 },
             ),
             vec![
@@ -272,20 +769,24 @@ mod tests {
         );
         assert_eq!(
             build_fret_board(
-                Tuning::StandardB7,
+                &TuningChoice::BuiltIn(Tuning::StandardB7),
+                &Capo::default(),
                 7,
-                &[
-                    (Note::A, 0),
-                    (Note::B, 2),
-                    (Note::CSharp, 4),
-                    (Note::D, 5),
-                    (Note::E, 7),
-                    (Note::FSharp, 9),
-                    (Note::G, 10),
-                ],
+                &FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::A, &[0, 2, 4, 5, 7, 9, 10]),
+                    vec![
+                        SpelledNote::new(Letter::A, 0),
+                        SpelledNote::new(Letter::B, 0),
+                        SpelledNote::new(Letter::D, -1),
+                        SpelledNote::new(Letter::D, 0),
+                        SpelledNote::new(Letter::E, 0),
+                        SpelledNote::new(Letter::G, -1),
+                        SpelledNote::new(Letter::G, 0)
+                    ]
+                ),
                 &Format {
-                    flat: true,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes
                 },
             ),
             vec![
@@ -301,19 +802,23 @@ mod tests {
         );
         assert_eq!(
             build_fret_board(
-                Tuning::OpenE6,
+                &TuningChoice::BuiltIn(Tuning::OpenE6),
+                &Capo::default(),
                 15,
-                &[
-                    (Note::A, 0),
-                    (Note::ASharp, 1),
-                    (Note::CSharp, 4),
-                    (Note::DSharp, 6),
-                    (Note::FSharp, 9),
-                    (Note::GSharp, 11),
-                ],
+                &FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::A, &[0, 1, 4, 6, 9, 11]),
+                    vec![
+                        SpelledNote::new(Letter::A, 0),
+                        SpelledNote::new(Letter::A, 1),
+                        SpelledNote::new(Letter::C, 1),
+                        SpelledNote::new(Letter::D, 1),
+                        SpelledNote::new(Letter::F, 1),
+                        SpelledNote::new(Letter::G, 1)
+                    ]
+                ),
                 &Format {
-                    flat: false,
-                    colored: false
+                    colored: false,
+                    labels: Labels::Notes
                 },
             ),
             vec![
@@ -327,4 +832,289 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_build_fret_board_with_double_accidentals() {
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::StandardE6),
+                &Capo::default(),
+                0,
+                &FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::C, &[0, 1, 3, 4, 6, 8, 9]),
+                    vec![
+                        SpelledNote::new(Letter::C, 0),
+                        SpelledNote::new(Letter::D, -1),
+                        SpelledNote::new(Letter::E, -1),
+                        SpelledNote::new(Letter::F, -1),
+                        SpelledNote::new(Letter::G, -1),
+                        SpelledNote::new(Letter::A, -1),
+                        SpelledNote::new(Letter::B, -2)
+                    ]
+                ),
+                &Format {
+                    colored: false,
+                    labels: Labels::Notes
+                },
+            ),
+            vec![
+                "Fb-|----------|----Gb----|---------|---Ab----|",
+                "---|----C-----|----Db----|---------|---Eb----|",
+                "---|----Ab----|----Bbb---|---------|---------|",
+                "===|====Eb====|====Fb====|=========|===Gb====|",
+                "Bbb|==========|==========|===C=====|===Db====|",
+                "Fb=|==========|====Gb====|=========|===Ab====|",
+                "   |    1     |    2     |    3    |    4    |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_fret_board_with_labels() {
+        let notes = FretBoardNotes::new(
+            PitchClassSet::from_steps(Note::C, &[0, 2, 3, 5, 7, 8, 10]),
+            vec![
+                SpelledNote::new(Letter::C, 0),
+                SpelledNote::new(Letter::D, 0),
+                SpelledNote::new(Letter::E, -1),
+                SpelledNote::new(Letter::F, 0),
+                SpelledNote::new(Letter::G, 0),
+                SpelledNote::new(Letter::A, -1),
+                SpelledNote::new(Letter::B, -1),
+            ],
+        );
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::StandardE6),
+                &Capo::default(),
+                7,
+                &notes,
+                &Format {
+                    colored: false,
+                    labels: Labels::Degrees
+                },
+            ),
+            vec![
+                "|--------|---R----|--------|---2---|---b3--|",
+                "|--------|---5----|---b6---|-------|---b7--|",
+                "|---2----|---b3---|--------|---4---|-------|",
+                "|========|===b7===|========|===R===|=======|",
+                "|========|===4====|========|===5===|===b6==|",
+                "|========|===R====|========|===2===|===b3==|",
+                "|   7    |   8    |   9    |   10  |   11  |",
+            ]
+        );
+        assert_eq!(
+            get_note_labels(&notes, Labels::Intervals),
+            vec!["P1", "M2", "m3", "P4", "P5", "m6", "m7"]
+        );
+        assert_eq!(
+            get_note_labels(&notes, Labels::Semitones),
+            vec!["0", "2", "3", "5", "7", "8", "10"]
+        );
+    }
+
+    #[test]
+    fn test_build_bass_fret_board() {
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::BassStandardE4),
+                &Capo::default(),
+                0,
+                &FretBoardNotes::new(
+                    PitchClassSet::from_steps(Note::G, &[0, 2, 4, 7, 9]),
+                    spelled(&["G", "A", "B", "D", "E"])
+                ),
+                &Format {
+                    colored: false,
+                    labels: Labels::Notes
+                },
+            ),
+            vec![
+                "G=|==========|====A=====|=========|====B====|",
+                "D=|==========|====E=====|=========|=========|",
+                "A=|==========|====B=====|=========|=========|",
+                "E=|==========|==========|====G====|=========|",
+                "  |    1     |    2     |    3    |    4    |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_fret_board_with_capo() {
+        let g_major_pentatonic = FretBoardNotes::new(
+            PitchClassSet::from_steps(Note::G, &[0, 2, 4, 7, 9]),
+            spelled(&["G", "A", "B", "D", "E"]),
+        );
+        let format = Format {
+            colored: false,
+            labels: Labels::Notes,
+        };
+        let mut capo = Capo {
+            fret: 2,
+            numbering: FretNumbering::Relative,
+        };
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::BassStandardE4),
+                &capo,
+                0,
+                &g_major_pentatonic,
+                &format,
+            ),
+            vec![
+                "A=|=========|====B====|=========|========|",
+                "E=|=========|=========|====G====|========|",
+                "B=|=========|=========|====D====|========|",
+                "==|====G====|=========|====A====|========|",
+                "  |    1    |    2    |    3    |   4    |",
+            ]
+        );
+        capo.numbering = FretNumbering::Absolute;
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::BassStandardE4),
+                &capo,
+                0,
+                &g_major_pentatonic,
+                &format,
+            )[4],
+            "  |    3    |    4    |    5    |   6    |"
+        );
+        assert_eq!(capo.fret_number(3), 5);
+        assert_eq!(capo.fret_from_number(5), Some(3));
+        assert_eq!(capo.fret_from_number(1), None);
+    }
+
+    #[test]
+    fn test_build_extended_range_fret_board() {
+        let e_minor = FretBoardNotes::new(
+            PitchClassSet::from_steps(Note::E, &[0, 2, 3, 5, 7, 8, 10]),
+            spelled(&["E", "F#", "G", "A", "B", "C", "D"]),
+        );
+        let format = Format {
+            colored: false,
+            labels: Labels::Notes,
+        };
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::StandardFSharp8),
+                &Capo::default(),
+                0,
+                &e_minor,
+                &format
+            ),
+            vec![
+                "E-|----------|----F#----|----G----|---------|",
+                "B-|----C-----|----------|----D----|---------|",
+                "G-|----------|----A-----|---------|----B----|",
+                "D=|==========|====E=====|=========|====F#===|",
+                "A=|==========|====B=====|====C====|=========|",
+                "E=|==========|====F#====|====G====|=========|",
+                "B=|====C=====|==========|====D====|=========|",
+                "F#|====G=====|==========|====A====|=========|",
+                "  |    1     |    2     |    3    |    4    |",
+            ]
+        );
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::DropB9),
+                &Capo::default(),
+                0,
+                &e_minor,
+                &format
+            ),
+            vec![
+                "E-|----------|----F#----|----G----|---------|",
+                "B-|----C-----|----------|----D----|---------|",
+                "G-|----------|----A-----|---------|----B----|",
+                "D=|==========|====E=====|=========|====F#===|",
+                "A=|==========|====B=====|====C====|=========|",
+                "E=|==========|====F#====|====G====|=========|",
+                "B=|====C=====|==========|====D====|=========|",
+                "F#|====G=====|==========|====A====|=========|",
+                "B=|====C=====|==========|====D====|=========|",
+                "  |    1     |    2     |    3    |    4    |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_fret_board_with_emphasis() {
+        let notes = FretBoardNotes::new(
+            PitchClassSet::from_steps(Note::C, &[0, 2, 3, 5, 7, 8, 10]),
+            spelled(&["C", "D", "Eb", "F", "G", "Ab", "Bb"]),
+        )
+        .emphasize_degrees(&[3, 7]);
+        assert_eq!(
+            notes.emphasized,
+            vec![false, false, true, false, false, false, true]
+        );
+        assert_eq!(
+            build_fret_board(
+                &TuningChoice::BuiltIn(Tuning::StandardE6),
+                &Capo::default(),
+                7,
+                &notes,
+                &Format {
+                    colored: false,
+                    labels: Labels::Degrees
+                },
+            ),
+            vec![
+                "|--------|--R-----|--------|--2----|--[b3]-|",
+                "|--------|--5-----|--b6----|-------|--[b7]-|",
+                "|--2-----|--[b3]--|--------|--4----|-------|",
+                "|========|==[b7]==|========|==R====|=======|",
+                "|========|==4=====|========|==5====|==b6===|",
+                "|========|==R=====|========|==2====|==[b3]=|",
+                "|   7    |   8    |   9    |   10  |   11  |",
+            ]
+        );
+        assert!(notes.has_emphasis());
+        assert_eq!(
+            notes.clone().emphasize_degrees(&[10]).emphasized,
+            vec![false, false, true, false, false, false, false]
+        );
+        assert!(!notes.emphasize_degrees(&[0]).emphasized.contains(&true));
+    }
+
+    #[test]
+    fn test_build_edo_fret_board() {
+        let edo = Edo::new(24);
+        let rast = EdoScale::new(
+            edo,
+            "Rast",
+            "C".parse().unwrap(),
+            edo.map_quarter_tones(get_quarter_tone_steps_by_maqam(Maqam::Rast)),
+        );
+        assert_eq!(
+            build_edo_fret_board(
+                &TuningChoice::BuiltIn(Tuning::StandardE6),
+                &Capo::default(),
+                0,
+                &rast,
+                &Format {
+                    colored: false,
+                    labels: Labels::Notes
+                },
+            ),
+            vec![
+                "--|-----|--F--|-----|-----|-----|-G--|----|----|----|",
+                "--|-----|--C--|-----|-----|-----|-D--|----|----|-Ed-|",
+                "G-|-----|-----|-----|--A--|-----|----|-Bd-|----|----|",
+                "D=|=====|=====|==Ed=|=====|=====|=F==|====|====|====|",
+                "A=|=====|=====|==Bd=|=====|=====|=C==|====|====|====|",
+                "==|=====|==F==|=====|=====|=====|=G==|====|====|====|",
+                "  |  1  |  2  |  3  |  4  |  5  | 6  | 7  | 8  | 9  |",
+            ]
+        );
+        assert_eq!(get_edo_fret(Edo::new(19), 5), 7);
+        let capo = Capo {
+            fret: 2,
+            numbering: FretNumbering::Relative,
+        };
+        assert_eq!(capo.in_edo(Edo::new(19)).fret, 3);
+        assert_eq!(get_edo_starting_fret(Edo::new(19), &capo, 3), 4);
+    }
 }
@@ -0,0 +1,101 @@
+use clap::ValueEnum;
+
+use super::notes::Note;
+use super::pitches::Pitch;
+use super::scales::ScaleCategory;
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Instrument {
+    Guitar,
+    Bass,
+    Ukulele,
+    Mandolin,
+    Banjo,
+}
+
+pub const fn instrument_to_string(instrument: Instrument) -> &'static str {
+    match instrument {
+        Instrument::Guitar => "Guitar",
+        Instrument::Bass => "Bass",
+        Instrument::Ukulele => "Ukulele",
+        Instrument::Mandolin => "Mandolin",
+        Instrument::Banjo => "Banjo",
+    }
+}
+
+/// Returns the number of frets on the neck, which is at most `NUM_FRETS`.
+pub const fn get_num_frets(instrument: Instrument) -> usize {
+    match instrument {
+        Instrument::Guitar | Instrument::Bass => 24,
+        Instrument::Ukulele => 18,
+        Instrument::Mandolin => 20,
+        Instrument::Banjo => 22,
+    }
+}
+
+/// Returns the lowest pitch the thickest string of a custom tuning can have when it
+/// is written without an octave, so that it falls within the octave of the lowest
+/// strings of the instrument, e.g. between A1 and G#2 on guitar.
+pub const fn get_lowest_pitch_without_octave(instrument: Instrument) -> Pitch {
+    match instrument {
+        Instrument::Guitar => Pitch::new(Note::A, 1),
+        Instrument::Bass => Pitch::new(Note::A, 0),
+        Instrument::Ukulele => Pitch::new(Note::G, 3),
+        Instrument::Mandolin | Instrument::Banjo => Pitch::new(Note::C, 3),
+    }
+}
+
+/// Returns how many of the highest strings are plain and drawn thin (`-`). The
+/// other strings are wound and drawn thick (`=`).
+pub const fn get_num_thin_strings(instrument: Instrument) -> usize {
+    match instrument {
+        Instrument::Guitar => 3,
+        Instrument::Bass => 0,
+        Instrument::Ukulele => 4,
+        Instrument::Mandolin => 2,
+        Instrument::Banjo => 3,
+    }
+}
+
+/// The scale categories the scale of the day is picked from when none are selected.
+/// Bass lines lean on the modes and pentatonics rather than on bebop or symmetric
+/// scales.
+pub const fn get_default_categories(instrument: Instrument) -> &'static [ScaleCategory] {
+    match instrument {
+        Instrument::Guitar | Instrument::Ukulele | Instrument::Mandolin | Instrument::Banjo => &[
+            ScaleCategory::Diatonic,
+            ScaleCategory::Pentatonic,
+            ScaleCategory::MelodicMinor,
+            ScaleCategory::HarmonicMinor,
+            ScaleCategory::HarmonicMajor,
+            ScaleCategory::Symmetric,
+            ScaleCategory::Bebop,
+            ScaleCategory::World,
+        ],
+        Instrument::Bass => &[ScaleCategory::Diatonic, ScaleCategory::Pentatonic],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::fret_board::{FRET_SPAN, NUM_FRETS};
+
+    #[test]
+    fn test_get_default_categories() {
+        assert_eq!(
+            get_default_categories(Instrument::Guitar),
+            ScaleCategory::value_variants()
+        );
+        assert!(get_default_categories(Instrument::Bass).contains(&ScaleCategory::Pentatonic));
+        assert!(!get_default_categories(Instrument::Bass).contains(&ScaleCategory::Bebop));
+    }
+
+    #[test]
+    fn test_get_num_frets() {
+        for instrument in Instrument::value_variants() {
+            assert!(get_num_frets(*instrument) <= NUM_FRETS);
+            assert!(get_num_frets(*instrument) >= FRET_SPAN);
+        }
+    }
+}
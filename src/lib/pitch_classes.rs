@@ -0,0 +1,548 @@
+use super::notes::{Note, NOTES, NUM_NOTES};
+
+const FULL_MASK: u16 = (1 << NUM_NOTES) - 1;
+
+/// Returns the index of a note in `NOTES`, which is used as its pitch class.
+pub fn note_to_pitch_class(note: Note) -> usize {
+    NOTES.iter().position(|&n| n == note).unwrap()
+}
+
+const fn rotate_mask(mask: u16, semitones: usize) -> u16 {
+    let semitones = semitones % NUM_NOTES;
+    ((mask << semitones) | (mask >> (NUM_NOTES - semitones))) & FULL_MASK
+}
+
+/// Prime forms of the set classes of 3 to 6 notes, in the order of their Forte
+/// numbers. Larger sets are numbered after their complement.
+const FORTE_TRICHORDS: [[usize; 3]; 12] = [
+    [0, 1, 2],
+    [0, 1, 3],
+    [0, 1, 4],
+    [0, 1, 5],
+    [0, 1, 6],
+    [0, 2, 4],
+    [0, 2, 5],
+    [0, 2, 6],
+    [0, 2, 7],
+    [0, 3, 6],
+    [0, 3, 7],
+    [0, 4, 8],
+];
+
+const FORTE_TETRACHORDS: [[usize; 4]; 29] = [
+    [0, 1, 2, 3],
+    [0, 1, 2, 4],
+    [0, 1, 3, 4],
+    [0, 1, 2, 5],
+    [0, 1, 2, 6],
+    [0, 1, 2, 7],
+    [0, 1, 4, 5],
+    [0, 1, 5, 6],
+    [0, 1, 6, 7],
+    [0, 2, 3, 5],
+    [0, 1, 3, 5],
+    [0, 2, 3, 6],
+    [0, 1, 3, 6],
+    [0, 2, 3, 7],
+    [0, 1, 4, 6],
+    [0, 1, 5, 7],
+    [0, 3, 4, 7],
+    [0, 1, 4, 7],
+    [0, 1, 4, 8],
+    [0, 1, 5, 8],
+    [0, 2, 4, 6],
+    [0, 2, 4, 7],
+    [0, 2, 5, 7],
+    [0, 2, 4, 8],
+    [0, 2, 6, 8],
+    [0, 3, 5, 8],
+    [0, 2, 5, 8],
+    [0, 3, 6, 9],
+    [0, 1, 3, 7],
+];
+
+const FORTE_PENTACHORDS: [[usize; 5]; 38] = [
+    [0, 1, 2, 3, 4],
+    [0, 1, 2, 3, 5],
+    [0, 1, 2, 4, 5],
+    [0, 1, 2, 3, 6],
+    [0, 1, 2, 3, 7],
+    [0, 1, 2, 5, 6],
+    [0, 1, 2, 6, 7],
+    [0, 2, 3, 4, 6],
+    [0, 1, 2, 4, 6],
+    [0, 1, 3, 4, 6],
+    [0, 2, 3, 4, 7],
+    [0, 1, 3, 5, 6],
+    [0, 1, 2, 4, 8],
+    [0, 1, 2, 5, 7],
+    [0, 1, 2, 6, 8],
+    [0, 1, 3, 4, 7],
+    [0, 1, 3, 4, 8],
+    [0, 1, 4, 5, 7],
+    [0, 1, 3, 6, 7],
+    [0, 1, 3, 7, 8],
+    [0, 1, 4, 5, 8],
+    [0, 1, 4, 7, 8],
+    [0, 2, 3, 5, 7],
+    [0, 1, 3, 5, 7],
+    [0, 2, 3, 5, 8],
+    [0, 2, 4, 5, 8],
+    [0, 1, 3, 5, 8],
+    [0, 2, 3, 6, 8],
+    [0, 1, 3, 6, 8],
+    [0, 1, 4, 6, 8],
+    [0, 1, 3, 6, 9],
+    [0, 1, 4, 6, 9],
+    [0, 2, 4, 6, 8],
+    [0, 2, 4, 6, 9],
+    [0, 2, 4, 7, 9],
+    [0, 1, 2, 4, 7],
+    [0, 3, 4, 5, 8],
+    [0, 1, 2, 5, 8],
+];
+
+const FORTE_HEXACHORDS: [[usize; 6]; 50] = [
+    [0, 1, 2, 3, 4, 5],
+    [0, 1, 2, 3, 4, 6],
+    [0, 1, 2, 3, 5, 6],
+    [0, 1, 2, 4, 5, 6],
+    [0, 1, 2, 3, 6, 7],
+    [0, 1, 2, 5, 6, 7],
+    [0, 1, 2, 6, 7, 8],
+    [0, 2, 3, 4, 5, 7],
+    [0, 1, 2, 3, 5, 7],
+    [0, 1, 3, 4, 5, 7],
+    [0, 1, 2, 4, 5, 7],
+    [0, 1, 2, 4, 6, 7],
+    [0, 1, 3, 4, 6, 7],
+    [0, 1, 3, 4, 5, 8],
+    [0, 1, 2, 4, 5, 8],
+    [0, 1, 4, 5, 6, 8],
+    [0, 1, 2, 4, 7, 8],
+    [0, 1, 2, 5, 7, 8],
+    [0, 1, 3, 4, 7, 8],
+    [0, 1, 4, 5, 8, 9],
+    [0, 2, 3, 4, 6, 8],
+    [0, 1, 2, 4, 6, 8],
+    [0, 2, 3, 5, 6, 8],
+    [0, 1, 3, 4, 6, 8],
+    [0, 1, 3, 5, 6, 8],
+    [0, 1, 3, 5, 7, 8],
+    [0, 1, 3, 4, 6, 9],
+    [0, 1, 3, 5, 6, 9],
+    [0, 1, 3, 6, 8, 9],
+    [0, 1, 3, 6, 7, 9],
+    [0, 1, 4, 5, 7, 9],
+    [0, 2, 4, 5, 7, 9],
+    [0, 2, 3, 5, 7, 9],
+    [0, 1, 3, 5, 7, 9],
+    [0, 2, 4, 6, 8, 10],
+    [0, 1, 2, 3, 4, 7],
+    [0, 1, 2, 3, 4, 8],
+    [0, 1, 2, 3, 7, 8],
+    [0, 2, 3, 4, 5, 8],
+    [0, 1, 2, 3, 5, 8],
+    [0, 1, 2, 3, 6, 8],
+    [0, 1, 2, 3, 6, 9],
+    [0, 1, 2, 5, 6, 8],
+    [0, 1, 2, 5, 6, 9],
+    [0, 2, 3, 4, 6, 9],
+    [0, 1, 2, 4, 6, 9],
+    [0, 1, 2, 4, 7, 9],
+    [0, 1, 2, 5, 7, 9],
+    [0, 1, 3, 4, 7, 9],
+    [0, 1, 4, 6, 7, 9],
+];
+
+/// Forte numbers of the set classes of 3 to 6 notes that share their interval
+/// vector with another set class.
+const FORTE_Z_NUMBERS: [(usize, usize); 38] = [
+    (4, 15),
+    (4, 29),
+    (5, 12),
+    (5, 17),
+    (5, 18),
+    (5, 36),
+    (5, 37),
+    (5, 38),
+    (6, 3),
+    (6, 4),
+    (6, 6),
+    (6, 10),
+    (6, 11),
+    (6, 12),
+    (6, 13),
+    (6, 17),
+    (6, 19),
+    (6, 23),
+    (6, 24),
+    (6, 25),
+    (6, 26),
+    (6, 28),
+    (6, 29),
+    (6, 36),
+    (6, 37),
+    (6, 38),
+    (6, 39),
+    (6, 40),
+    (6, 41),
+    (6, 42),
+    (6, 43),
+    (6, 44),
+    (6, 45),
+    (6, 46),
+    (6, 47),
+    (6, 48),
+    (6, 49),
+    (6, 50),
+];
+
+fn find_forte_ordinal<const N: usize>(
+    prime_forms: &[[usize; N]],
+    prime_form: &[usize],
+) -> Option<usize> {
+    prime_forms
+        .iter()
+        .position(|candidate| {
+            PitchClassSet::from_steps(NOTES[0], candidate).prime_form() == prime_form
+        })
+        .map(|index| index + 1)
+}
+
+/// A set of pitch classes stored as a 12-bit mask, with bit `i` standing for
+/// `NOTES[i]`, together with the pitch class its steps are counted from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PitchClassSet {
+    mask: u16,
+    root: usize,
+}
+
+impl PitchClassSet {
+    pub fn from_steps(root: Note, steps: &[usize]) -> Self {
+        let mask = steps
+            .iter()
+            .fold(0, |mask, step| mask | 1 << (step % NUM_NOTES));
+        let root = note_to_pitch_class(root);
+        PitchClassSet {
+            mask: rotate_mask(mask, root),
+            root,
+        }
+    }
+
+    /// Builds a set rooted on the first note, or on `NOTES[0]` if there are none.
+    pub fn from_notes(notes: &[Note]) -> Self {
+        let mask = notes
+            .iter()
+            .fold(0, |mask, note| mask | 1 << note_to_pitch_class(*note));
+        PitchClassSet {
+            mask,
+            root: notes.first().map_or(0, |note| note_to_pitch_class(*note)),
+        }
+    }
+
+    pub const fn mask(&self) -> u16 {
+        self.mask
+    }
+
+    pub const fn root(&self) -> Note {
+        NOTES[self.root]
+    }
+
+    pub fn with_root(self, root: Note) -> Self {
+        PitchClassSet {
+            mask: self.mask,
+            root: note_to_pitch_class(root),
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// The mask with bit `i` standing for the note `i` semitones above the root.
+    const fn relative_mask(&self) -> u16 {
+        rotate_mask(self.mask, NUM_NOTES - self.root)
+    }
+
+    pub fn contains(&self, note: Note) -> bool {
+        self.mask & 1 << note_to_pitch_class(note) != 0
+    }
+
+    /// Returns the number of semitones from the root to the note if it is in the set.
+    pub fn step_of(&self, note: Note) -> Option<usize> {
+        let step = (note_to_pitch_class(note) + NUM_NOTES - self.root) % NUM_NOTES;
+        self.contains(note).then_some(step)
+    }
+
+    /// Returns the index of the note in `steps` if it is in the set.
+    pub fn position(&self, note: Note) -> Option<usize> {
+        let step = self.step_of(note)?;
+        Some((self.relative_mask() & ((1 << step) - 1)).count_ones() as usize)
+    }
+
+    /// Returns the semitones from the root to each note of the set, in ascending order.
+    pub fn steps(&self) -> Vec<usize> {
+        let relative_mask = self.relative_mask();
+        (0..NUM_NOTES)
+            .filter(|step| relative_mask & 1 << step != 0)
+            .collect()
+    }
+
+    /// Returns the notes of the set in ascending order from the root.
+    pub fn notes(&self) -> Vec<Note> {
+        self.steps()
+            .into_iter()
+            .map(|step| NOTES[(self.root + step) % NUM_NOTES])
+            .collect()
+    }
+
+    pub const fn transpose(self, semitones: usize) -> Self {
+        PitchClassSet {
+            mask: rotate_mask(self.mask, semitones),
+            root: (self.root + semitones) % NUM_NOTES,
+        }
+    }
+
+    /// Mirrors the set around its root, e.g. a major triad becomes a minor triad
+    /// with the root as its fifth.
+    pub const fn invert(self) -> Self {
+        let relative_mask = self.relative_mask();
+        let mut inverted = 0;
+        let mut step = 0;
+        while step < NUM_NOTES {
+            if relative_mask & 1 << step != 0 {
+                inverted |= 1 << ((NUM_NOTES - step) % NUM_NOTES);
+            }
+            step += 1;
+        }
+        PitchClassSet {
+            mask: rotate_mask(inverted, self.root),
+            root: self.root,
+        }
+    }
+
+    pub const fn complement(self) -> Self {
+        PitchClassSet {
+            mask: !self.mask & FULL_MASK,
+            root: self.root,
+        }
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        PitchClassSet {
+            mask: self.mask | other.mask,
+            root: self.root,
+        }
+    }
+
+    pub const fn intersection(self, other: Self) -> Self {
+        PitchClassSet {
+            mask: self.mask & other.mask,
+            root: self.root,
+        }
+    }
+
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.mask & other.mask == self.mask
+    }
+
+    /// Counts the pairs of notes of the set by interval class, from 1 to 6 semitones.
+    pub fn interval_vector(&self) -> [usize; 6] {
+        let mut interval_vector = [0; 6];
+        let steps = self.steps();
+        for (i, low) in steps.iter().enumerate() {
+            for high in &steps[i + 1..] {
+                let interval = high - low;
+                interval_vector[interval.min(NUM_NOTES - interval) - 1] += 1;
+            }
+        }
+        interval_vector
+    }
+
+    /// Returns the prime form of the set: of every transposition to 0 of the set
+    /// and of its inversion, the one packed the most to the left, comparing the
+    /// highest steps first (Rahn's ordering).
+    pub fn prime_form(&self) -> Vec<usize> {
+        let inverted = self.invert();
+        let prime_mask = (0..NUM_NOTES)
+            .flat_map(|root| {
+                [self.with_root(NOTES[root]), inverted.with_root(NOTES[root])]
+                    .into_iter()
+                    .filter(move |set| set.mask & 1 << root != 0)
+                    .map(|set| set.relative_mask())
+            })
+            .min()
+            .unwrap_or(0);
+        (0..NUM_NOTES)
+            .filter(|step| prime_mask & 1 << step != 0)
+            .collect()
+    }
+
+    /// Returns the Forte number of the set class, e.g. "7-35", with a Z for the set
+    /// classes that share their interval vector with another one.
+    pub fn forte_number(&self) -> Option<String> {
+        let len = self.len();
+        let ordinal = self.forte_ordinal()?;
+        let is_z = FORTE_Z_NUMBERS.contains(&(len.min(NUM_NOTES - len), ordinal));
+        Some(format!(
+            "{}-{}{}",
+            len,
+            if is_z { "Z" } else { "" },
+            ordinal
+        ))
+    }
+
+    fn forte_ordinal(&self) -> Option<usize> {
+        let prime_form = self.prime_form();
+        match self.len() {
+            0 => None,
+            1 | 11 | 12 => Some(1),
+            2 => self
+                .interval_vector()
+                .iter()
+                .position(|count| *count > 0)
+                .map(|index| index + 1),
+            3 => find_forte_ordinal(&FORTE_TRICHORDS, &prime_form),
+            4 => find_forte_ordinal(&FORTE_TETRACHORDS, &prime_form),
+            5 => find_forte_ordinal(&FORTE_PENTACHORDS, &prime_form),
+            6 => find_forte_ordinal(&FORTE_HEXACHORDS, &prime_form),
+            _ => self.complement().forte_ordinal(),
+        }
+    }
+
+    /// Returns how many different sets the transpositions of the set give, which is
+    /// less than 12 for sets that repeat within the octave, e.g. 2 for the whole
+    /// tone scale.
+    pub fn num_distinct_transpositions(&self) -> usize {
+        (1..=NUM_NOTES)
+            .find(|semitones| self.transpose(*semitones).mask == self.mask)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const C_MAJOR: [usize; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+    #[test]
+    fn test_pitch_class_set() {
+        let c_major = PitchClassSet::from_steps(Note::C, &C_MAJOR);
+        assert_eq!(c_major.len(), 7);
+        assert_eq!(c_major.steps(), C_MAJOR);
+        assert_eq!(c_major.root(), Note::C);
+        assert!(c_major.contains(Note::F));
+        assert!(!c_major.contains(Note::FSharp));
+        assert_eq!(c_major.step_of(Note::A), Some(9));
+        assert_eq!(c_major.position(Note::A), Some(5));
+        assert_eq!(c_major.position(Note::ASharp), None);
+        assert_eq!(
+            PitchClassSet::from_notes(&[Note::D, Note::FSharp, Note::A]).steps(),
+            vec![0, 4, 7]
+        );
+    }
+
+    #[test]
+    fn test_pitch_class_set_operations() {
+        let c_major = PitchClassSet::from_steps(Note::C, &C_MAJOR);
+        let g_major = c_major.transpose(7);
+        assert_eq!(g_major.root(), Note::G);
+        assert_eq!(g_major.steps(), C_MAJOR);
+        assert!(g_major.contains(Note::FSharp));
+
+        let c_major_triad = PitchClassSet::from_steps(Note::C, &[0, 4, 7]);
+        assert_eq!(
+            c_major_triad.invert(),
+            PitchClassSet::from_steps(Note::C, &[0, 5, 8])
+        );
+        assert_eq!(c_major.complement().steps(), vec![1, 3, 6, 8, 10]);
+        assert!(c_major_triad.is_subset(&c_major));
+        assert!(!c_major.is_subset(&c_major_triad));
+        assert_eq!(c_major.intersection(g_major).len(), 6);
+        assert_eq!(c_major.union(g_major).len(), 8);
+    }
+
+    #[test]
+    fn test_forte_number() {
+        let forte_number =
+            |steps: &[usize]| PitchClassSet::from_steps(Note::C, steps).forte_number();
+        assert_eq!(forte_number(&C_MAJOR), Some(String::from("7-35")));
+        assert_eq!(forte_number(&[0, 2, 4, 7, 9]), Some(String::from("5-35")));
+        assert_eq!(forte_number(&[0, 4, 7]), Some(String::from("3-11")));
+        assert_eq!(forte_number(&[0, 1, 4, 7]), Some(String::from("4-18")));
+        assert_eq!(forte_number(&[0, 1, 4, 6]), Some(String::from("4-Z15")));
+        assert_eq!(
+            forte_number(&[0, 2, 3, 5, 7, 9, 11]),
+            Some(String::from("7-34"))
+        );
+        assert_eq!(
+            forte_number(&[0, 2, 3, 5, 6, 8, 9, 11]),
+            Some(String::from("8-28"))
+        );
+        assert_eq!(
+            forte_number(&[0, 1, 2, 4, 5, 6]),
+            Some(String::from("6-Z4"))
+        );
+        assert_eq!(forte_number(&[0, 6]), Some(String::from("2-6")));
+        assert_eq!(forte_number(&[]), None);
+    }
+
+    #[test]
+    fn test_forte_prime_forms_cover_every_set_class() {
+        let tables: [Vec<&[usize]>; 4] = [
+            FORTE_TRICHORDS.iter().map(|p| p.as_slice()).collect(),
+            FORTE_TETRACHORDS.iter().map(|p| p.as_slice()).collect(),
+            FORTE_PENTACHORDS.iter().map(|p| p.as_slice()).collect(),
+            FORTE_HEXACHORDS.iter().map(|p| p.as_slice()).collect(),
+        ];
+        for table in tables {
+            let len = table[0].len();
+            let mut prime_forms = table
+                .iter()
+                .map(|steps| PitchClassSet::from_steps(Note::C, steps).prime_form())
+                .collect::<Vec<Vec<usize>>>();
+            prime_forms.sort();
+            prime_forms.dedup();
+            let mut all_prime_forms = (0..1 << NUM_NOTES)
+                .map(|mask| PitchClassSet { mask, root: 0 })
+                .filter(|set| set.len() == len)
+                .map(|set| set.prime_form())
+                .collect::<Vec<Vec<usize>>>();
+            all_prime_forms.sort();
+            all_prime_forms.dedup();
+            assert_eq!(prime_forms, all_prime_forms);
+        }
+    }
+
+    #[test]
+    fn test_num_distinct_transpositions() {
+        let num_distinct_transpositions = |steps: &[usize]| {
+            PitchClassSet::from_steps(Note::C, steps).num_distinct_transpositions()
+        };
+        assert_eq!(num_distinct_transpositions(&C_MAJOR), 12);
+        assert_eq!(num_distinct_transpositions(&[0, 2, 4, 6, 8, 10]), 2);
+        assert_eq!(num_distinct_transpositions(&[0, 2, 3, 5, 6, 8, 9, 11]), 3);
+        assert_eq!(num_distinct_transpositions(&[0, 3, 4, 7, 8, 11]), 4);
+    }
+
+    #[test]
+    fn test_interval_vector_and_prime_form() {
+        let c_major = PitchClassSet::from_steps(Note::C, &C_MAJOR);
+        assert_eq!(c_major.interval_vector(), [2, 5, 4, 3, 6, 1]);
+        assert_eq!(c_major.prime_form(), vec![0, 1, 3, 5, 6, 8, 10]);
+        assert_eq!(
+            PitchClassSet::from_steps(Note::E, &[0, 4, 7]).prime_form(),
+            vec![0, 3, 7]
+        );
+        assert_eq!(
+            PitchClassSet::from_steps(Note::A, &[0, 1, 5, 6, 8]).prime_form(),
+            vec![0, 1, 5, 6, 8]
+        );
+    }
+}
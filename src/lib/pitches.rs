@@ -0,0 +1,112 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::notes::{note_to_string, Note, NOTES, NUM_NOTES};
+use super::pitch_classes::note_to_pitch_class;
+use super::spelling::{letter_to_note, split_note_name, SpelledNote};
+
+/// The usual concert pitch of A4, in Hz.
+pub const DEFAULT_A4_FREQUENCY: f64 = 440.0;
+
+const A4_MIDI_NUMBER: i32 = 69;
+
+/// The index of C in `NOTES`, where octave numbers change.
+const C_PITCH_CLASS: usize = 3;
+
+/// A note in a given octave, in scientific pitch notation where C4 is middle C.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Pitch {
+    pub note: Note,
+    pub octave: i32,
+}
+
+impl Pitch {
+    pub const fn new(note: Note, octave: i32) -> Self {
+        Pitch { note, octave }
+    }
+
+    pub fn from_midi_number(midi_number: i32) -> Self {
+        let semitones_above_c = midi_number.rem_euclid(NUM_NOTES as i32) as usize;
+        Pitch {
+            note: NOTES[(semitones_above_c + C_PITCH_CLASS) % NUM_NOTES],
+            octave: midi_number.div_euclid(NUM_NOTES as i32) - 1,
+        }
+    }
+
+    pub fn midi_number(&self) -> i32 {
+        let semitones_above_c =
+            (note_to_pitch_class(self.note) + NUM_NOTES - C_PITCH_CLASS) % NUM_NOTES;
+        (self.octave + 1) * NUM_NOTES as i32 + semitones_above_c as i32
+    }
+
+    /// Returns the frequency in Hz in equal temperament with A4 tuned to `a4_frequency`.
+    pub fn frequency(&self, a4_frequency: f64) -> f64 {
+        let semitones_from_a4 = self.midi_number() - A4_MIDI_NUMBER;
+        a4_frequency * 2f64.powf(semitones_from_a4 as f64 / NUM_NOTES as f64)
+    }
+
+    pub fn transpose(&self, semitones: i32) -> Self {
+        Pitch::from_midi_number(self.midi_number() + semitones)
+    }
+}
+
+impl fmt::Display for Pitch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", note_to_string(self.note, false), self.octave)
+    }
+}
+
+impl FromStr for Pitch {
+    type Err = String;
+
+    /// Parses a pitch such as "E2", "f#3" or "Bb-1". The octave follows the letter,
+    /// so "Cb4" is the same pitch as "B3".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (note_name, octave) = split_note_name(s.trim());
+        let note = note_name.parse::<SpelledNote>()?;
+        let octave = octave
+            .parse::<i32>()
+            .map_err(|_| format!("\"{}\" is not a valid pitch", s))?;
+        let natural = Pitch::new(letter_to_note(note.letter), octave);
+        Ok(natural.transpose(note.alteration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_midi_number() {
+        assert_eq!(Pitch::new(Note::C, 4).midi_number(), 60);
+        assert_eq!(Pitch::new(Note::E, 2).midi_number(), 40);
+        assert_eq!(Pitch::from_midi_number(21), Pitch::new(Note::A, 0));
+        assert_eq!(Pitch::from_midi_number(59), Pitch::new(Note::B, 3));
+        assert_eq!(Pitch::new(Note::B, 3).transpose(1), Pitch::new(Note::C, 4));
+    }
+
+    #[test]
+    fn test_frequency() {
+        assert_eq!(
+            Pitch::new(Note::A, 4).frequency(DEFAULT_A4_FREQUENCY),
+            440.0
+        );
+        assert_eq!(
+            Pitch::new(Note::A, 2).frequency(DEFAULT_A4_FREQUENCY),
+            110.0
+        );
+        assert_eq!(Pitch::new(Note::A, 4).frequency(432.0), 432.0);
+        assert!((Pitch::new(Note::E, 2).frequency(DEFAULT_A4_FREQUENCY) - 82.407).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_pitch() {
+        assert_eq!("E2".parse(), Ok(Pitch::new(Note::E, 2)));
+        assert_eq!("f#3".parse(), Ok(Pitch::new(Note::FSharp, 3)));
+        assert_eq!("Cb4".parse(), Ok(Pitch::new(Note::B, 3)));
+        assert_eq!("C-1".parse(), Ok(Pitch::new(Note::C, -1)));
+        assert!("E".parse::<Pitch>().is_err());
+        assert!("H2".parse::<Pitch>().is_err());
+        assert_eq!(Pitch::new(Note::ASharp, 1).to_string(), "A#1");
+    }
+}
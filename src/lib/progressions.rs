@@ -0,0 +1,186 @@
+use super::chord_scales::{recommend_scales, ScaleRecommendation};
+use super::chords::Chord;
+use super::fret_board::FretBoardNotes;
+use super::notes::Note;
+use super::pitch_classes::PitchClassSet;
+use super::scales::ScaleChoice;
+use super::spelling::spell_scale;
+
+/// A chord of a progression and the scale played over it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgressionChord {
+    pub chord: Chord,
+    /// The scale played over the chord, if any scale fits it.
+    pub scale: Option<ScaleChoice>,
+    /// The notes of the scale with the chord tones emphasized, or only the chord
+    /// tones if no scale fits.
+    pub fret_board_notes: FretBoardNotes,
+}
+
+/// Splits a progression such as "Dm7 G7 Cmaj7" or "ii7 | V7 | Imaj7" into its chords.
+pub fn split_progression(progression: &str) -> Vec<&str> {
+    progression
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '|')
+        .filter(|chord| !chord.is_empty())
+        .collect()
+}
+
+/// Picks the scale played over a chord. Within a key, it is the mode of the key on
+/// the root of the chord if it is among the recommendations. Otherwise it is among
+/// the scales that fit the chord as well as the first recommendation, preferring
+/// those within the key and then the one closest to seven notes, so that the chord
+/// is heard within a whole mode rather than a pentatonic scale.
+fn choose_progression_scale(
+    recommendations: &[ScaleRecommendation],
+    root: Note,
+    key: Option<PitchClassSet>,
+) -> Option<ScaleChoice> {
+    let pitch_classes = |recommendation: &ScaleRecommendation| {
+        PitchClassSet::from_steps(root, recommendation.scale.steps())
+    };
+    if let Some(key) = key {
+        let mode_of_key = recommendations
+            .iter()
+            .find(|recommendation| pitch_classes(recommendation).mask() == key.mask());
+        if let Some(recommendation) = mode_of_key {
+            return Some(recommendation.scale.clone());
+        }
+    }
+    let fit = |recommendation: &ScaleRecommendation| {
+        (
+            recommendation.missing_tensions.len(),
+            recommendation.avoid_notes.len(),
+        )
+    };
+    let best_fit = fit(recommendations.first()?);
+    recommendations
+        .iter()
+        .filter(|recommendation| fit(recommendation) == best_fit)
+        .min_by_key(|recommendation| {
+            (
+                key.is_some_and(|key| !pitch_classes(recommendation).is_subset(&key)),
+                recommendation.scale.steps().len().abs_diff(7),
+            )
+        })
+        .map(|recommendation| recommendation.scale.clone())
+}
+
+/// Returns a chord of a progression with the scale played over it, picked from
+/// `scales`. `key` holds the notes of the key the chord was written in as a roman
+/// numeral, if it was.
+pub fn get_progression_chord(
+    chord: Chord,
+    scales: &[ScaleChoice],
+    key: Option<PitchClassSet>,
+) -> ProgressionChord {
+    let scale = choose_progression_scale(
+        &recommend_scales(&chord.degrees, scales),
+        chord.root.note(),
+        key,
+    );
+    let fret_board_notes = match scale {
+        Some(ref scale) => {
+            let steps = scale.steps();
+            FretBoardNotes::new(
+                PitchClassSet::from_steps(chord.root.note(), steps),
+                spell_scale(chord.root, steps),
+            )
+            .emphasize_steps(&chord.intervals())
+        }
+        None => FretBoardNotes::from_chord(&chord),
+    };
+    ProgressionChord {
+        chord,
+        scale,
+        fret_board_notes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::chords::parse_chord_symbol;
+    use crate::lib::scales::Scale;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_split_progression() {
+        assert_eq!(
+            split_progression("Dm7 G7  Cmaj7"),
+            vec!["Dm7", "G7", "Cmaj7"]
+        );
+        assert_eq!(
+            split_progression("ii7 | V7 | Imaj7"),
+            vec!["ii7", "V7", "Imaj7"]
+        );
+    }
+
+    #[test]
+    fn test_get_progression_chord() {
+        let catalog = Scale::value_variants()
+            .iter()
+            .copied()
+            .map(ScaleChoice::BuiltIn)
+            .collect::<Vec<ScaleChoice>>();
+        let dm7 = parse_chord_symbol("Dm7").unwrap();
+        assert_eq!(
+            recommend_scales(&dm7.degrees, &catalog)[0].scale,
+            ScaleChoice::BuiltIn(Scale::PentatonicMinor)
+        );
+        let progression_chord = get_progression_chord(dm7, &catalog, None);
+        assert_eq!(
+            progression_chord.scale,
+            Some(ScaleChoice::BuiltIn(Scale::Dorian))
+        );
+        assert_eq!(
+            progression_chord.fret_board_notes.emphasized,
+            vec![true, false, true, false, true, false, true]
+        );
+
+        let progression_chord =
+            get_progression_chord(parse_chord_symbol("C(b9,#9)").unwrap(), &[], None);
+        assert_eq!(progression_chord.scale, None);
+        assert!(progression_chord
+            .fret_board_notes
+            .emphasized
+            .iter()
+            .all(|emphasized| *emphasized));
+    }
+
+    #[test]
+    fn test_get_progression_chord_in_key() {
+        let catalog = Scale::value_variants()
+            .iter()
+            .copied()
+            .map(ScaleChoice::BuiltIn)
+            .collect::<Vec<ScaleChoice>>();
+        let c_major = PitchClassSet::from_steps(Note::C, &[0, 2, 4, 5, 7, 9, 11]);
+        let scales = ["Dm7", "G7", "Cmaj7"]
+            .iter()
+            .map(|symbol| {
+                get_progression_chord(parse_chord_symbol(symbol).unwrap(), &catalog, Some(c_major))
+                    .scale
+            })
+            .collect::<Vec<Option<ScaleChoice>>>();
+        assert_eq!(
+            scales,
+            [Scale::Dorian, Scale::Mixolydian, Scale::Major]
+                .map(|scale| Some(ScaleChoice::BuiltIn(scale)))
+        );
+        assert_ne!(
+            get_progression_chord(parse_chord_symbol("G7").unwrap(), &catalog, None).scale,
+            Some(ScaleChoice::BuiltIn(Scale::Mixolydian))
+        );
+
+        let c_pentatonic_major = PitchClassSet::from_steps(Note::C, &[0, 2, 4, 7, 9]);
+        assert_eq!(
+            get_progression_chord(
+                parse_chord_symbol("Am").unwrap(),
+                &catalog,
+                Some(c_pentatonic_major)
+            )
+            .scale,
+            Some(ScaleChoice::BuiltIn(Scale::PentatonicMinor))
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use super::scales::{get_mode_steps, ScaleChoice};
+use super::spelling::{get_degrees_by_steps, Degree};
+
+/// How a scale relates to the other scales of a catalog. Degrees count from 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaleRelations {
+    /// The scales matching each mode of the scale, by the degree the mode starts on.
+    /// The relative modes start on that degree, the parallel modes on the same root.
+    pub modes: Vec<(usize, Vec<ScaleChoice>)>,
+    /// The first scale of the catalog that the scale is a mode of, the degree of the
+    /// scale its root is on and the number of the mode, counting from 1.
+    pub parent: Option<(ScaleChoice, usize, usize)>,
+    /// Scales on the same root that only differ by one note moved by a semitone,
+    /// with the degree of the scale that is replaced and the degree replacing it.
+    pub neighbors: Vec<(ScaleChoice, Degree, Degree)>,
+}
+
+pub fn find_scales_with_steps(steps: &[usize], scales: &[ScaleChoice]) -> Vec<ScaleChoice> {
+    scales
+        .iter()
+        .filter(|scale| scale.steps() == steps)
+        .cloned()
+        .collect()
+}
+
+pub fn get_scale_relations(steps: &[usize], scales: &[ScaleChoice]) -> ScaleRelations {
+    let modes = (1..steps.len())
+        .map(|degree| {
+            let mode_steps = get_mode_steps(steps, degree);
+            (degree, find_scales_with_steps(&mode_steps, scales))
+        })
+        .filter(|(_, mode_scales)| !mode_scales.is_empty())
+        .collect();
+
+    let parent = scales.iter().find_map(|scale| {
+        let degree =
+            (0..steps.len()).find(|degree| get_mode_steps(steps, *degree) == scale.steps())?;
+        Some((
+            scale.clone(),
+            degree,
+            (steps.len() - degree) % steps.len() + 1,
+        ))
+    });
+
+    let degrees = get_degrees_by_steps(steps);
+    let neighbors = scales
+        .iter()
+        .filter(|scale| scale.steps().len() == steps.len())
+        .filter_map(|scale| {
+            let removed = steps
+                .iter()
+                .position(|step| !scale.steps().contains(step))?;
+            let added = scale
+                .steps()
+                .iter()
+                .position(|step| !steps.contains(step))?;
+            let is_one_note_apart = steps
+                .iter()
+                .enumerate()
+                .all(|(i, step)| i == removed || scale.steps().contains(step))
+                && steps[removed].abs_diff(scale.steps()[added]) == 1;
+            is_one_note_apart.then(|| {
+                (
+                    scale.clone(),
+                    degrees[removed],
+                    get_degrees_by_steps(scale.steps())[added],
+                )
+            })
+        })
+        .collect();
+
+    ScaleRelations {
+        modes,
+        parent,
+        neighbors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::scales::Scale;
+    use clap::ValueEnum;
+
+    fn catalog() -> Vec<ScaleChoice> {
+        Scale::value_variants()
+            .iter()
+            .copied()
+            .map(ScaleChoice::BuiltIn)
+            .collect()
+    }
+
+    #[test]
+    fn test_get_scale_relations() {
+        let relations = get_scale_relations(&[0, 2, 3, 5, 7, 9, 10], &catalog());
+        assert_eq!(
+            relations.modes[0],
+            (1, vec![ScaleChoice::BuiltIn(Scale::Phrygian)])
+        );
+        assert_eq!(
+            relations.modes[5],
+            (
+                6,
+                vec![
+                    ScaleChoice::BuiltIn(Scale::Major),
+                    ScaleChoice::BuiltIn(Scale::Ionian)
+                ]
+            )
+        );
+        assert_eq!(
+            relations.parent,
+            Some((ScaleChoice::BuiltIn(Scale::Major), 6, 2))
+        );
+        assert!(relations.neighbors.contains(&(
+            ScaleChoice::BuiltIn(Scale::Aeolian),
+            Degree::new(6, 0),
+            Degree::new(6, -1)
+        )));
+        assert!(relations.neighbors.contains(&(
+            ScaleChoice::BuiltIn(Scale::MelodicMinor),
+            Degree::new(7, -1),
+            Degree::new(7, 0)
+        )));
+    }
+
+    #[test]
+    fn test_get_scale_relations_of_altered() {
+        let relations = get_scale_relations(&[0, 1, 3, 4, 6, 8, 10], &catalog());
+        assert_eq!(
+            relations.parent,
+            Some((ScaleChoice::BuiltIn(Scale::MelodicMinor), 1, 7))
+        );
+    }
+}
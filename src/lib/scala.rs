@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::Path;
+
+use super::notes::NUM_NOTES;
+use super::scales::CustomScale;
+
+const CENTS_PER_OCTAVE: f64 = 1200.0;
+
+/// How far in cents the last note of a scale may be from the octave.
+const OCTAVE_TOLERANCE: f64 = 0.5;
+
+/// A scale read from a Scala `.scl` file, with the cents of each note above the
+/// root, up to and including the octave.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalaScale {
+    pub name: String,
+    pub description: String,
+    pub cents: Vec<f64>,
+}
+
+impl ScalaScale {
+    /// Returns the cents of the notes within the octave, starting with the root.
+    pub fn note_cents(&self) -> Vec<f64> {
+        std::iter::once(0.0)
+            .chain(self.cents[..self.cents.len() - 1].iter().copied())
+            .collect()
+    }
+
+    /// Maps every note to the nearest 12-TET step. Notes that fall on the same step
+    /// are kept once.
+    pub fn steps(&self) -> Vec<usize> {
+        let mut steps = self
+            .note_cents()
+            .iter()
+            .map(|cents| (cents / 100.0).round() as usize % NUM_NOTES)
+            .collect::<Vec<usize>>();
+        steps.sort_unstable();
+        steps.dedup();
+        steps
+    }
+
+    pub fn to_custom_scale(&self) -> CustomScale {
+        CustomScale {
+            name: self.name.clone(),
+            steps: self.steps(),
+        }
+    }
+}
+
+/// Returns whether a `--custom-scales` entry is the path of a Scala file.
+pub fn is_scala_file(arg_scale: &str) -> bool {
+    Path::new(arg_scale)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("scl"))
+}
+
+/// Loads a Scala file, named after the file without its extension.
+pub fn load_scala_file(path: &Path) -> Result<ScalaScale, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read Scala file {}: {}", path.display(), err))?;
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    parse_scala(&name, &contents)
+        .map_err(|err| format!("Could not parse Scala file {}: {}", path.display(), err))
+}
+
+/// Parses the contents of a Scala file: a description line, the number of notes and
+/// a line for each note above the root, in cents ("701.955") or as a ratio ("3/2"
+/// or "2"). Lines starting with "!" are comments.
+pub fn parse_scala(name: &str, contents: &str) -> Result<ScalaScale, String> {
+    let mut lines = contents.lines().filter(|line| !line.starts_with('!'));
+    let description = lines
+        .next()
+        .ok_or("The description is missing")?
+        .trim()
+        .to_string();
+    let num_notes = lines
+        .next()
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|count| count.parse::<usize>().ok())
+        .ok_or("The number of notes is missing")?;
+    let cents = lines
+        .filter_map(|line| line.split_whitespace().next())
+        .map(parse_scala_pitch)
+        .collect::<Result<Vec<f64>, String>>()?;
+    if cents.len() != num_notes || num_notes == 0 {
+        return Err(format!(
+            "Expected {} notes but found {}",
+            num_notes,
+            cents.len()
+        ));
+    }
+    let period = cents[cents.len() - 1];
+    if (period - CENTS_PER_OCTAVE).abs() > OCTAVE_TOLERANCE {
+        return Err(format!(
+            "Only scales that repeat at the octave are supported, this one repeats at {} cents",
+            period
+        ));
+    }
+    Ok(ScalaScale {
+        name: String::from(name),
+        description,
+        cents,
+    })
+}
+
+fn parse_scala_pitch(pitch: &str) -> Result<f64, String> {
+    let invalid_pitch = || format!("\"{}\" is not a valid pitch", pitch);
+    if pitch.contains('.') {
+        return pitch.parse::<f64>().map_err(|_| invalid_pitch());
+    }
+    let (numerator, denominator) = pitch.split_once('/').unwrap_or((pitch, "1"));
+    let numerator = numerator.parse::<u64>().map_err(|_| invalid_pitch())?;
+    let denominator = denominator.parse::<u64>().map_err(|_| invalid_pitch())?;
+    if numerator == 0 || denominator == 0 {
+        return Err(invalid_pitch());
+    }
+    Ok(CENTS_PER_OCTAVE * (numerator as f64 / denominator as f64).log2())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PYTHAGOREAN_PENTATONIC: &str = "! pyth_5.scl
+!
+Pythagorean pentatonic
+ 5
+!
+ 9/8
+ 81/64
+ 3/2
+ 905.865 major sixth
+ 2/1
+";
+
+    #[test]
+    fn test_parse_scala() {
+        let scale = parse_scala("pyth_5", PYTHAGOREAN_PENTATONIC).unwrap();
+        assert_eq!(scale.description, "Pythagorean pentatonic");
+        assert_eq!(scale.cents.len(), 5);
+        assert!((scale.cents[2] - 701.955).abs() < 0.001);
+        assert_eq!(scale.steps(), vec![0, 2, 4, 7, 9]);
+        assert_eq!(
+            scale.to_custom_scale(),
+            CustomScale {
+                name: String::from("pyth_5"),
+                steps: vec![0, 2, 4, 7, 9],
+            }
+        );
+
+        assert!(parse_scala("", "Too few\n3\n3/2\n2/1\n").is_err());
+        assert!(parse_scala("", "Tritave\n1\n3/1\n").is_err());
+        assert!(parse_scala("", "Invalid\n2\n3/0\n2/1\n").is_err());
+        assert!(is_scala_file("scales/pyth_5.SCL"));
+        assert!(!is_scala_file("Pyth=0,2,4,7,9"));
+    }
+}
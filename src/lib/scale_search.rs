@@ -0,0 +1,198 @@
+use clap::ValueEnum;
+
+use super::notes::{Note, NUM_NOTES};
+use super::pitch_classes::PitchClassSet;
+use super::relations::find_scales_with_steps;
+use super::scales::{CustomScale, ScaleChoice};
+use super::spelling::get_degrees_by_steps;
+
+/// The number of pitch class sets that contain the root, one for each choice of
+/// the other eleven notes.
+pub const NUM_ROOTED_SETS: usize = 1 << (NUM_NOTES - 1);
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum Symmetry {
+    /// The scale repeats within the octave, like the whole tone scale.
+    Transpositional,
+    /// The scale is its own mirror image around the root, like Dorian.
+    Inversional,
+}
+
+/// Limits which pitch class sets a search returns. Every limit that is set must hold.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScaleFilter {
+    pub num_notes: Option<Vec<usize>>,
+    /// The longest run of half steps in a row, counting across the octave.
+    pub max_consecutive_semitones: Option<usize>,
+    /// Semitones above the root that the scale must contain.
+    pub steps: Vec<usize>,
+    pub symmetry: Option<Symmetry>,
+}
+
+impl ScaleFilter {
+    pub fn matches(&self, steps: &[usize]) -> bool {
+        let pitch_classes = PitchClassSet::from_steps(Note::C, steps);
+        self.num_notes
+            .as_ref()
+            .is_none_or(|num_notes| num_notes.contains(&steps.len()))
+            && self
+                .max_consecutive_semitones
+                .is_none_or(|max| get_max_consecutive_semitones(steps) <= max)
+            && self.steps.iter().all(|step| steps.contains(step))
+            && self.symmetry.is_none_or(|symmetry| match symmetry {
+                Symmetry::Transpositional => {
+                    pitch_classes.num_distinct_transpositions() < NUM_NOTES
+                }
+                Symmetry::Inversional => pitch_classes.invert() == pitch_classes,
+            })
+    }
+}
+
+/// A pitch class set found by a search, with the scales of the catalog that have
+/// the same steps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaleSearchResult {
+    pub steps: Vec<usize>,
+    pub names: Vec<ScaleChoice>,
+}
+
+impl ScaleSearchResult {
+    /// Returns the first scale of the catalog with these steps, or a custom scale
+    /// named after its formula, e.g. "Scale 1 b2 3 4 5 b6 7".
+    pub fn to_scale_choice(&self) -> ScaleChoice {
+        self.names.first().cloned().unwrap_or_else(|| {
+            ScaleChoice::Custom(CustomScale {
+                name: format!("Scale {}", get_formula(&self.steps)),
+                steps: self.steps.clone(),
+            })
+        })
+    }
+}
+
+/// Returns the degrees of a scale separated by spaces, e.g. "1 2 b3 4 5 6 b7".
+pub fn get_formula(steps: &[usize]) -> String {
+    get_degrees_by_steps(steps)
+        .iter()
+        .map(|degree| degree.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn get_max_consecutive_semitones(steps: &[usize]) -> usize {
+    if steps.len() == NUM_NOTES {
+        return NUM_NOTES;
+    }
+    let is_half_step = |degree: usize| {
+        let next_step = steps.get(degree + 1).copied().unwrap_or(NUM_NOTES);
+        next_step - steps[degree] == 1
+    };
+    // Starting after a larger step means no run wraps around the octave.
+    let start = (0..steps.len())
+        .find(|degree| !is_half_step(*degree))
+        .unwrap();
+    let mut max = 0;
+    let mut run = 0;
+    for offset in 1..=steps.len() {
+        if is_half_step((start + offset) % steps.len()) {
+            run += 1;
+            max = max.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    max
+}
+
+/// Enumerates every pitch class set containing the root that passes the filter, by
+/// number of notes and then by steps, naming them after the scales of the catalog.
+pub fn search_scales(filter: &ScaleFilter, scales: &[ScaleChoice]) -> Vec<ScaleSearchResult> {
+    let mut results = (0..NUM_ROOTED_SETS)
+        .map(|mask| {
+            std::iter::once(0)
+                .chain((1..NUM_NOTES).filter(|step| mask & 1 << (step - 1) != 0))
+                .collect::<Vec<usize>>()
+        })
+        .filter(|steps| filter.matches(steps))
+        .map(|steps| ScaleSearchResult {
+            names: find_scales_with_steps(&steps, scales),
+            steps,
+        })
+        .collect::<Vec<ScaleSearchResult>>();
+    results.sort_by(|a, b| (a.steps.len(), &a.steps).cmp(&(b.steps.len(), &b.steps)));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::scales::Scale;
+
+    #[test]
+    fn test_get_max_consecutive_semitones() {
+        assert_eq!(get_max_consecutive_semitones(&[0, 2, 4, 5, 7, 9, 11]), 1);
+        assert_eq!(get_max_consecutive_semitones(&[0, 2, 4, 7, 9]), 0);
+        assert_eq!(
+            get_max_consecutive_semitones(&[0, 2, 4, 5, 7, 9, 10, 11]),
+            3
+        );
+        assert_eq!(get_max_consecutive_semitones(&[0, 1, 2, 6]), 2);
+        assert_eq!(get_max_consecutive_semitones(&[0]), 0);
+        assert_eq!(
+            get_max_consecutive_semitones(&(0..NUM_NOTES).collect::<Vec<usize>>()),
+            NUM_NOTES
+        );
+    }
+
+    #[test]
+    fn test_search_scales() {
+        let catalog = [
+            ScaleChoice::BuiltIn(Scale::Major),
+            ScaleChoice::BuiltIn(Scale::Ionian),
+            ScaleChoice::BuiltIn(Scale::WholeTone),
+        ];
+        assert_eq!(
+            search_scales(&ScaleFilter::default(), &catalog).len(),
+            NUM_ROOTED_SETS
+        );
+
+        let heptatonic = ScaleFilter {
+            num_notes: Some(vec![7]),
+            max_consecutive_semitones: Some(1),
+            ..ScaleFilter::default()
+        };
+        let results = search_scales(&heptatonic, &catalog);
+        assert!(results.iter().all(|result| result.steps.len() == 7));
+        let major = results
+            .iter()
+            .find(|result| !result.names.is_empty())
+            .unwrap();
+        assert_eq!(major.steps, vec![0, 2, 4, 5, 7, 9, 11]);
+        assert_eq!(major.names, catalog[..2]);
+
+        let symmetric = ScaleFilter {
+            steps: vec![2],
+            symmetry: Some(Symmetry::Transpositional),
+            ..ScaleFilter::default()
+        };
+        let results = search_scales(&symmetric, &catalog);
+        assert!(results.iter().any(|result| result.names == catalog[2..]));
+        assert!(results
+            .iter()
+            .all(|result| result.steps.contains(&2) && result.steps.contains(&8)));
+        let unnamed = results
+            .iter()
+            .find(|result| result.names.is_empty())
+            .unwrap();
+        assert_eq!(unnamed.steps, vec![0, 2, 6, 8]);
+        assert_eq!(unnamed.to_scale_choice().name(), "Scale 1 2 #4 #5");
+
+        let inversional = ScaleFilter {
+            num_notes: Some(vec![7]),
+            symmetry: Some(Symmetry::Inversional),
+            ..ScaleFilter::default()
+        };
+        assert!(search_scales(&inversional, &[])
+            .iter()
+            .any(|result| result.steps == [0, 2, 3, 5, 7, 9, 10]));
+    }
+}
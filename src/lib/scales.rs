@@ -1,6 +1,9 @@
 use clap::ValueEnum;
 
-#[derive(Copy, Clone, Debug, ValueEnum)]
+use super::notes::{Note, NUM_NOTES};
+use super::pitch_classes::PitchClassSet;
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
 pub enum Scale {
     Major,
     HarmonicMinor,
@@ -19,33 +22,178 @@ pub enum Scale {
     Mixolydian,
     Aeolian,
     Locrian,
+    DorianFlat2,
+    LydianAugmented,
+    LydianDominant,
+    MixolydianFlat6,
+    LocrianSharp2,
+    Altered,
+    LocrianNatural6,
+    IonianSharp5,
+    DorianSharp4,
+    PhrygianDominant,
+    LydianSharp2,
+    AlteredDiminished,
+    HarmonicMajor,
+    DorianFlat5,
+    PhrygianFlat4,
+    LydianFlat3,
+    MixolydianFlat2,
+    LydianAugmentedSharp2,
+    LocrianDoubleFlat7,
+    HungarianMinor,
+    HungarianMajor,
+    DoubleHarmonic,
+    NeapolitanMajor,
+    NeapolitanMinor,
+    Persian,
+    Enigmatic,
+    Prometheus,
+    Hirajoshi,
+    InSen,
+    Iwato,
+    BebopDominant,
+    BebopMajor,
+    BebopDorian,
+    BebopMelodicMinor,
+    WholeTone,
+    Augmented,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum ScaleCategory {
+    Diatonic,
+    Pentatonic,
+    MelodicMinor,
+    HarmonicMinor,
+    HarmonicMajor,
+    Symmetric,
+    Bebop,
+    World,
+}
+
+pub const fn category_to_string(category: ScaleCategory) -> &'static str {
+    match category {
+        ScaleCategory::Diatonic => "Diatonic",
+        ScaleCategory::Pentatonic => "Pentatonic",
+        ScaleCategory::MelodicMinor => "Melodic minor modes",
+        ScaleCategory::HarmonicMinor => "Harmonic minor modes",
+        ScaleCategory::HarmonicMajor => "Harmonic major modes",
+        ScaleCategory::Symmetric => "Symmetric",
+        ScaleCategory::Bebop => "Bebop",
+        ScaleCategory::World => "World",
+    }
+}
+
+pub const fn get_category_by_scale(scale: Scale) -> ScaleCategory {
+    match scale {
+        Scale::Major
+        | Scale::NaturalMinor
+        | Scale::Ionian
+        | Scale::Dorian
+        | Scale::Phrygian
+        | Scale::Lydian
+        | Scale::Mixolydian
+        | Scale::Aeolian
+        | Scale::Locrian => ScaleCategory::Diatonic,
+        Scale::PentatonicMajor
+        | Scale::PentatonicMinor
+        | Scale::PentatonicBlues
+        | Scale::PentatonicNeutral => ScaleCategory::Pentatonic,
+        Scale::MelodicMinor
+        | Scale::DorianFlat2
+        | Scale::LydianAugmented
+        | Scale::LydianDominant
+        | Scale::MixolydianFlat6
+        | Scale::LocrianSharp2
+        | Scale::Altered => ScaleCategory::MelodicMinor,
+        Scale::HarmonicMinor
+        | Scale::LocrianNatural6
+        | Scale::IonianSharp5
+        | Scale::DorianSharp4
+        | Scale::PhrygianDominant
+        | Scale::LydianSharp2
+        | Scale::AlteredDiminished => ScaleCategory::HarmonicMinor,
+        Scale::HarmonicMajor
+        | Scale::DorianFlat5
+        | Scale::PhrygianFlat4
+        | Scale::LydianFlat3
+        | Scale::MixolydianFlat2
+        | Scale::LydianAugmentedSharp2
+        | Scale::LocrianDoubleFlat7 => ScaleCategory::HarmonicMajor,
+        Scale::WholeDiminished | Scale::HalfDiminished | Scale::WholeTone | Scale::Augmented => {
+            ScaleCategory::Symmetric
+        }
+        Scale::BebopDominant
+        | Scale::BebopMajor
+        | Scale::BebopDorian
+        | Scale::BebopMelodicMinor => ScaleCategory::Bebop,
+        Scale::HungarianMinor
+        | Scale::HungarianMajor
+        | Scale::DoubleHarmonic
+        | Scale::NeapolitanMajor
+        | Scale::NeapolitanMinor
+        | Scale::Persian
+        | Scale::Enigmatic
+        | Scale::Prometheus
+        | Scale::Hirajoshi
+        | Scale::InSen
+        | Scale::Iwato => ScaleCategory::World,
+    }
+}
+
+pub fn get_scales_by_category(category: ScaleCategory) -> Vec<Scale> {
+    Scale::value_variants()
+        .iter()
+        .copied()
+        .filter(|scale| get_category_by_scale(*scale) == category)
+        .collect()
 }
 
-pub const SCALES: [Scale; 17] = [
-    Scale::Major,
-    Scale::HarmonicMinor,
-    Scale::MelodicMinor,
-    Scale::NaturalMinor,
-    Scale::PentatonicMajor,
-    Scale::PentatonicMinor,
-    Scale::PentatonicBlues,
-    Scale::PentatonicNeutral,
-    Scale::WholeDiminished,
-    Scale::HalfDiminished,
-    Scale::Ionian,
-    Scale::Dorian,
-    Scale::Phrygian,
-    Scale::Lydian,
-    Scale::Mixolydian,
-    Scale::Aeolian,
-    Scale::Locrian,
-];
+const MELODIC_MINOR_STEPS: [usize; 7] = [0, 2, 3, 5, 7, 9, 11];
+const HARMONIC_MINOR_STEPS: [usize; 7] = [0, 2, 3, 5, 7, 8, 11];
+const HARMONIC_MAJOR_STEPS: [usize; 7] = [0, 2, 4, 5, 7, 8, 11];
+
+const MELODIC_MINOR_MODES: [[usize; 7]; 7] = get_modes(MELODIC_MINOR_STEPS);
+const HARMONIC_MINOR_MODES: [[usize; 7]; 7] = get_modes(HARMONIC_MINOR_STEPS);
+const HARMONIC_MAJOR_MODES: [[usize; 7]; 7] = get_modes(HARMONIC_MAJOR_STEPS);
+
+/// Rotates the steps of a scale so that its `degree`-th note (counting from 0)
+/// becomes the new root, e.g. degree 4 of Major gives Mixolydian.
+pub const fn rotate_steps<const N: usize>(steps: [usize; N], degree: usize) -> [usize; N] {
+    let mut rotated = [0; N];
+    let offset = steps[degree];
+    let mut i = 0;
+    while i < N {
+        rotated[i] = (steps[(degree + i) % N] + NUM_NOTES - offset) % NUM_NOTES;
+        i += 1;
+    }
+    rotated
+}
+
+/// Same as `rotate_steps` for scales whose length is only known at runtime.
+pub fn get_mode_steps(steps: &[usize], degree: usize) -> Vec<usize> {
+    (0..steps.len())
+        .map(|i| (steps[(degree + i) % steps.len()] + NUM_NOTES - steps[degree]) % NUM_NOTES)
+        .collect()
+}
+
+/// Returns every mode of a scale, indexed by the degree it starts on.
+pub const fn get_modes<const N: usize>(steps: [usize; N]) -> [[usize; N]; N] {
+    let mut modes = [[0; N]; N];
+    let mut degree = 0;
+    while degree < N {
+        modes[degree] = rotate_steps(steps, degree);
+        degree += 1;
+    }
+    modes
+}
 
 pub const fn get_steps_by_scale(key: Scale) -> &'static [usize] {
     match key {
         Scale::Major => &[0, 2, 4, 5, 7, 9, 11],
-        Scale::HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
-        Scale::MelodicMinor => &[0, 2, 3, 5, 7, 9, 11],
+        Scale::HarmonicMinor => &HARMONIC_MINOR_STEPS,
+        Scale::MelodicMinor => &MELODIC_MINOR_STEPS,
         Scale::NaturalMinor => &[0, 2, 3, 5, 7, 8, 10],
         Scale::PentatonicMajor => &[0, 2, 4, 7, 9],
         Scale::PentatonicMinor => &[0, 3, 5, 7, 10],
@@ -60,6 +208,53 @@ pub const fn get_steps_by_scale(key: Scale) -> &'static [usize] {
         Scale::Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
         Scale::Aeolian => &[0, 2, 3, 5, 7, 8, 10],
         Scale::Locrian => &[0, 1, 3, 5, 6, 8, 10],
+        Scale::DorianFlat2 => &MELODIC_MINOR_MODES[1],
+        Scale::LydianAugmented => &MELODIC_MINOR_MODES[2],
+        Scale::LydianDominant => &MELODIC_MINOR_MODES[3],
+        Scale::MixolydianFlat6 => &MELODIC_MINOR_MODES[4],
+        Scale::LocrianSharp2 => &MELODIC_MINOR_MODES[5],
+        Scale::Altered => &MELODIC_MINOR_MODES[6],
+        Scale::LocrianNatural6 => &HARMONIC_MINOR_MODES[1],
+        Scale::IonianSharp5 => &HARMONIC_MINOR_MODES[2],
+        Scale::DorianSharp4 => &HARMONIC_MINOR_MODES[3],
+        Scale::PhrygianDominant => &HARMONIC_MINOR_MODES[4],
+        Scale::LydianSharp2 => &HARMONIC_MINOR_MODES[5],
+        Scale::AlteredDiminished => &HARMONIC_MINOR_MODES[6],
+        Scale::HarmonicMajor => &HARMONIC_MAJOR_MODES[0],
+        Scale::DorianFlat5 => &HARMONIC_MAJOR_MODES[1],
+        Scale::PhrygianFlat4 => &HARMONIC_MAJOR_MODES[2],
+        Scale::LydianFlat3 => &HARMONIC_MAJOR_MODES[3],
+        Scale::MixolydianFlat2 => &HARMONIC_MAJOR_MODES[4],
+        Scale::LydianAugmentedSharp2 => &HARMONIC_MAJOR_MODES[5],
+        Scale::LocrianDoubleFlat7 => &HARMONIC_MAJOR_MODES[6],
+        Scale::HungarianMinor => &[0, 2, 3, 6, 7, 8, 11],
+        Scale::HungarianMajor => &[0, 3, 4, 6, 7, 9, 10],
+        Scale::DoubleHarmonic => &[0, 1, 4, 5, 7, 8, 11],
+        Scale::NeapolitanMajor => &[0, 1, 3, 5, 7, 9, 11],
+        Scale::NeapolitanMinor => &[0, 1, 3, 5, 7, 8, 11],
+        Scale::Persian => &[0, 1, 4, 5, 6, 8, 11],
+        Scale::Enigmatic => &[0, 1, 4, 6, 8, 10, 11],
+        Scale::Prometheus => &[0, 2, 4, 6, 9, 10],
+        Scale::Hirajoshi => &[0, 2, 3, 7, 8],
+        Scale::InSen => &[0, 1, 5, 7, 10],
+        Scale::Iwato => &[0, 1, 5, 6, 10],
+        Scale::BebopDominant => &[0, 2, 4, 5, 7, 9, 10, 11],
+        Scale::BebopMajor => &[0, 2, 4, 5, 7, 8, 9, 11],
+        Scale::BebopDorian => &[0, 2, 3, 4, 5, 7, 9, 10],
+        Scale::BebopMelodicMinor => &[0, 2, 3, 5, 7, 8, 9, 11],
+        Scale::WholeTone => &[0, 2, 4, 6, 8, 10],
+        Scale::Augmented => &[0, 3, 4, 7, 8, 11],
+    }
+}
+
+/// Returns the chromatic passing tone a bebop scale adds to its seven note parent
+/// scale, which its chords are built from.
+pub const fn get_passing_step_by_scale(scale: Scale) -> Option<usize> {
+    match scale {
+        Scale::BebopDominant => Some(11),
+        Scale::BebopMajor | Scale::BebopMelodicMinor => Some(8),
+        Scale::BebopDorian => Some(4),
+        _ => None,
     }
 }
 
@@ -82,9 +277,195 @@ pub const fn scale_to_string(scale: Scale) -> &'static str {
         Scale::Mixolydian => "Mixolydian",
         Scale::Aeolian => "Aeolian",
         Scale::Locrian => "Locrian",
+        Scale::DorianFlat2 => "Dorian b2",
+        Scale::LydianAugmented => "Lydian Augmented",
+        Scale::LydianDominant => "Lydian Dominant",
+        Scale::MixolydianFlat6 => "Mixolydian b6",
+        Scale::LocrianSharp2 => "Locrian #2",
+        Scale::Altered => "Altered",
+        Scale::LocrianNatural6 => "Locrian Natural 6",
+        Scale::IonianSharp5 => "Ionian #5",
+        Scale::DorianSharp4 => "Dorian #4",
+        Scale::PhrygianDominant => "Phrygian Dominant",
+        Scale::LydianSharp2 => "Lydian #2",
+        Scale::AlteredDiminished => "Altered Diminished",
+        Scale::HarmonicMajor => "Harmonic Major",
+        Scale::DorianFlat5 => "Dorian b5",
+        Scale::PhrygianFlat4 => "Phrygian b4",
+        Scale::LydianFlat3 => "Lydian b3",
+        Scale::MixolydianFlat2 => "Mixolydian b2",
+        Scale::LydianAugmentedSharp2 => "Lydian Augmented #2",
+        Scale::LocrianDoubleFlat7 => "Locrian bb7",
+        Scale::HungarianMinor => "Hungarian Minor",
+        Scale::HungarianMajor => "Hungarian Major",
+        Scale::DoubleHarmonic => "Double Harmonic",
+        Scale::NeapolitanMajor => "Neapolitan Major",
+        Scale::NeapolitanMinor => "Neapolitan Minor",
+        Scale::Persian => "Persian",
+        Scale::Enigmatic => "Enigmatic",
+        Scale::Prometheus => "Prometheus",
+        Scale::Hirajoshi => "Hirajoshi",
+        Scale::InSen => "In Sen",
+        Scale::Iwato => "Iwato",
+        Scale::BebopDominant => "Bebop Dominant",
+        Scale::BebopMajor => "Bebop Major",
+        Scale::BebopDorian => "Bebop Dorian",
+        Scale::BebopMelodicMinor => "Bebop Melodic Minor",
+        Scale::WholeTone => "Whole Tone",
+        Scale::Augmented => "Augmented",
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomScale {
+    pub name: String,
+    pub steps: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScaleChoice {
+    BuiltIn(Scale),
+    Custom(CustomScale),
+}
+
+impl ScaleChoice {
+    pub fn steps(&self) -> &[usize] {
+        match self {
+            ScaleChoice::BuiltIn(scale) => get_steps_by_scale(*scale),
+            ScaleChoice::Custom(custom_scale) => &custom_scale.steps,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ScaleChoice::BuiltIn(scale) => scale_to_string(*scale),
+            ScaleChoice::Custom(custom_scale) => &custom_scale.name,
+        }
+    }
+
+    /// Returns the steps the chords of the scale are built from, leaving out the
+    /// passing tone of a bebop scale.
+    pub fn harmony_steps(&self) -> Vec<usize> {
+        let passing_step = match self {
+            ScaleChoice::BuiltIn(scale) => get_passing_step_by_scale(*scale),
+            ScaleChoice::Custom(_) => None,
+        };
+        self.steps()
+            .iter()
+            .copied()
+            .filter(|step| Some(*step) != passing_step)
+            .collect()
+    }
+}
+
+/// Finds every root and scale whose notes contain all of `notes`, ranked by how many
+/// notes the scale adds. Roots are tried starting from the first of `notes`, and
+/// scales with the same steps as an earlier one, like Ionian after Major, are skipped.
+pub fn find_scales_containing(notes: &[Note], scales: &[ScaleChoice]) -> Vec<(Note, ScaleChoice)> {
+    let pitch_classes = PitchClassSet::from_notes(notes);
+    let mut matches = Vec::new();
+    let mut seen_steps: Vec<&[usize]> = Vec::new();
+    for scale in scales {
+        if seen_steps.contains(&scale.steps()) {
+            continue;
+        }
+        seen_steps.push(scale.steps());
+        let scale_pitch_classes = PitchClassSet::from_steps(pitch_classes.root(), scale.steps());
+        for semitones in 0..NUM_NOTES {
+            let transposed = scale_pitch_classes.transpose(semitones);
+            if pitch_classes.is_subset(&transposed) {
+                matches.push((transposed.root(), scale.clone()));
+            }
+        }
+    }
+    matches.sort_by_key(|(_, scale)| scale.steps().len());
+    matches
+}
+
+/// Parses a custom scale definition in the form `NAME=FORMULA`.
+pub fn parse_custom_scale(definition: &str) -> Result<CustomScale, String> {
+    let (name, formula) = definition.split_once('=').ok_or(format!(
+        "\"{}\" is not in the form NAME=FORMULA",
+        definition
+    ))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("\"{}\" is missing a scale name", definition));
+    }
+    let steps = parse_scale_formula(formula)
+        .map_err(|err| format!("Invalid formula for scale \"{}\": {}", name, err))?;
+    Ok(CustomScale {
+        name: String::from(name),
+        steps,
+    })
+}
+
+/// Parses either a comma separated list of semitones ("0,2,3,5,7,8,11") or a
+/// space separated interval formula ("1 2 b3 4 5 b6 7") into sorted steps.
+pub fn parse_scale_formula(formula: &str) -> Result<Vec<usize>, String> {
+    let formula = formula.trim();
+    let mut steps = if formula.contains(',') {
+        formula
+            .split(',')
+            .map(|semitone| {
+                let semitone = semitone.trim();
+                semitone
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|step| *step < NUM_NOTES)
+                    .ok_or(format!(
+                        "\"{}\" is not a semitone between 0 and 11",
+                        semitone
+                    ))
+            })
+            .collect::<Result<Vec<usize>, String>>()?
+    } else {
+        formula
+            .split_whitespace()
+            .map(parse_interval)
+            .collect::<Result<Vec<usize>, String>>()?
+    };
+    if !steps.contains(&0) {
+        return Err(String::from("the formula must contain the root"));
+    }
+    let num_steps = steps.len();
+    steps.sort_unstable();
+    steps.dedup();
+    if steps.len() != num_steps {
+        return Err(String::from("the formula contains the same note twice"));
+    }
+    if steps.len() < 2 {
+        return Err(String::from("the formula must contain at least two notes"));
+    }
+    Ok(steps)
+}
+
+/// Parses a single interval such as "b3", "#4" or "13" into semitones above the root.
+pub fn parse_interval(interval: &str) -> Result<usize, String> {
+    let degree_start = interval
+        .find(|c: char| c.is_ascii_digit())
+        .ok_or(format!("\"{}\" is not a valid interval", interval))?;
+    let (accidentals, degree) = interval.split_at(degree_start);
+    let alteration = accidentals
+        .chars()
+        .try_fold(0i32, |alteration, c| match c {
+            'b' => Ok(alteration - 1),
+            '#' => Ok(alteration + 1),
+            _ => Err(format!("\"{}\" is not a valid interval", interval)),
+        })?;
+    let semitones = match degree {
+        "1" | "8" => 0,
+        "2" | "9" => 2,
+        "3" | "10" => 4,
+        "4" | "11" => 5,
+        "5" | "12" => 7,
+        "6" | "13" => 9,
+        "7" | "14" => 11,
+        _ => return Err(format!("\"{}\" is not a valid interval", interval)),
+    };
+    Ok((semitones + alteration).rem_euclid(NUM_NOTES as i32) as usize)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,9 +479,140 @@ mod tests {
         assert_eq!(get_steps_by_scale(Scale::Phrygian), &[0, 1, 3, 5, 7, 8, 10]);
     }
 
+    #[test]
+    fn test_rotate_steps() {
+        assert_eq!(
+            rotate_steps([0, 2, 4, 5, 7, 9, 11], 4),
+            [0, 2, 4, 5, 7, 9, 10]
+        );
+        assert_eq!(get_mode_steps(&[0, 2, 4, 7, 9], 4), vec![0, 3, 5, 7, 10]);
+        assert_eq!(get_steps_by_scale(Scale::Altered), &[0, 1, 3, 4, 6, 8, 10]);
+        assert_eq!(
+            get_steps_by_scale(Scale::PhrygianDominant),
+            &[0, 1, 4, 5, 7, 8, 10]
+        );
+        assert_eq!(
+            get_steps_by_scale(Scale::LydianFlat3),
+            &[0, 2, 3, 6, 7, 9, 11]
+        );
+    }
+
+    #[test]
+    fn test_all_scales_are_valid() {
+        for scale in Scale::value_variants() {
+            let steps = get_steps_by_scale(*scale);
+            assert_eq!(steps[0], 0, "{:?}", scale);
+            assert!(
+                steps.windows(2).all(|pair| pair[0] < pair[1]),
+                "{:?}",
+                scale
+            );
+            assert!(steps.iter().all(|step| *step < NUM_NOTES), "{:?}", scale);
+        }
+    }
+
+    #[test]
+    fn test_get_scales_by_category() {
+        assert_eq!(
+            get_scales_by_category(ScaleCategory::Bebop),
+            vec![
+                Scale::BebopDominant,
+                Scale::BebopMajor,
+                Scale::BebopDorian,
+                Scale::BebopMelodicMinor
+            ]
+        );
+        assert_eq!(
+            get_category_by_scale(Scale::Altered),
+            ScaleCategory::MelodicMinor
+        );
+        assert!(Scale::value_variants().len() >= 50);
+    }
+
+    #[test]
+    fn test_harmony_steps() {
+        assert_eq!(
+            ScaleChoice::BuiltIn(Scale::BebopDominant).harmony_steps(),
+            get_steps_by_scale(Scale::Mixolydian)
+        );
+        assert_eq!(
+            ScaleChoice::BuiltIn(Scale::BebopMajor).harmony_steps(),
+            get_steps_by_scale(Scale::Major)
+        );
+        assert_eq!(
+            ScaleChoice::BuiltIn(Scale::BebopDorian).harmony_steps(),
+            get_steps_by_scale(Scale::Dorian)
+        );
+        assert_eq!(
+            ScaleChoice::BuiltIn(Scale::BebopMelodicMinor).harmony_steps(),
+            get_steps_by_scale(Scale::MelodicMinor)
+        );
+        assert_eq!(
+            ScaleChoice::BuiltIn(Scale::WholeTone).harmony_steps(),
+            get_steps_by_scale(Scale::WholeTone)
+        );
+    }
+
     #[test]
     fn test_scale_to_string() {
         assert_eq!(scale_to_string(Scale::PentatonicBlues), "Pentatonic Blues");
         assert_eq!(scale_to_string(Scale::Dorian), "Dorian");
     }
+
+    #[test]
+    fn test_find_scales_containing() {
+        let scales = [
+            Scale::Major,
+            Scale::PentatonicMinor,
+            Scale::BebopDominant,
+            Scale::Ionian,
+        ]
+        .map(ScaleChoice::BuiltIn);
+        assert_eq!(
+            find_scales_containing(&[Note::C, Note::E, Note::G, Note::ASharp, Note::D], &scales),
+            vec![
+                (Note::F, ScaleChoice::BuiltIn(Scale::Major)),
+                (Note::C, ScaleChoice::BuiltIn(Scale::BebopDominant)),
+                (Note::F, ScaleChoice::BuiltIn(Scale::BebopDominant)),
+            ]
+        );
+        assert_eq!(
+            find_scales_containing(&[Note::A, Note::C], &scales)
+                .iter()
+                .filter(|(_, scale)| *scale == ScaleChoice::BuiltIn(Scale::Major))
+                .map(|(root, _)| *root)
+                .collect::<Vec<Note>>(),
+            vec![Note::ASharp, Note::C, Note::F, Note::G]
+        );
+    }
+
+    #[test]
+    fn test_parse_scale_formula() {
+        assert_eq!(
+            parse_scale_formula("1 2 b3 4 5 b6 7"),
+            Ok(vec![0, 2, 3, 5, 7, 8, 11])
+        );
+        assert_eq!(parse_scale_formula("0, 7, 2,4,9"), Ok(vec![0, 2, 4, 7, 9]));
+        assert!(parse_scale_formula("2 b3 4").is_err());
+        assert!(parse_scale_formula("1 #4 b5").is_err());
+        assert!(parse_scale_formula("0,2,12").is_err());
+        assert!(parse_scale_formula("1 x3").is_err());
+    }
+
+    #[test]
+    fn test_parse_custom_scale() {
+        assert_eq!(
+            parse_custom_scale("Ukrainian Dorian=1 2 b3 #4 5 6 b7"),
+            Ok(CustomScale {
+                name: String::from("Ukrainian Dorian"),
+                steps: vec![0, 2, 3, 6, 7, 9, 10],
+            })
+        );
+        assert!(parse_custom_scale("1 2 3").is_err());
+        assert!(parse_custom_scale("=1 2 3").is_err());
+        assert_eq!(
+            ScaleChoice::BuiltIn(Scale::Dorian).steps(),
+            get_steps_by_scale(Scale::Dorian)
+        );
+    }
 }
@@ -0,0 +1,402 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::notes::{Accidental, Note, NOTES, NUM_NOTES};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Letter {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+
+pub const LETTERS: [Letter; 7] = [
+    Letter::A,
+    Letter::B,
+    Letter::C,
+    Letter::D,
+    Letter::E,
+    Letter::F,
+    Letter::G,
+];
+
+/// Steps of the major scale, used as the reference for each degree number.
+const MAJOR_STEPS: [usize; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+pub const fn letter_to_note(letter: Letter) -> Note {
+    match letter {
+        Letter::A => Note::A,
+        Letter::B => Note::B,
+        Letter::C => Note::C,
+        Letter::D => Note::D,
+        Letter::E => Note::E,
+        Letter::F => Note::F,
+        Letter::G => Note::G,
+    }
+}
+
+/// A note with a letter name and an alteration in semitones (-2 for a double flat,
+/// 2 for a double sharp), so that e.g. E# and F are told apart.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpelledNote {
+    pub letter: Letter,
+    pub alteration: i32,
+}
+
+impl SpelledNote {
+    pub const fn new(letter: Letter, alteration: i32) -> Self {
+        SpelledNote { letter, alteration }
+    }
+
+    pub fn note(&self) -> Note {
+        let natural_index = note_index(letter_to_note(self.letter)) as i32;
+        NOTES[(natural_index + self.alteration).rem_euclid(NUM_NOTES as i32) as usize]
+    }
+
+    /// Returns the letter `distance` letters above this note's letter.
+    fn letter_above(&self, distance: usize) -> Letter {
+        let letter_index = LETTERS.iter().position(|&l| l == self.letter).unwrap();
+        LETTERS[(letter_index + distance) % LETTERS.len()]
+    }
+}
+
+impl fmt::Display for SpelledNote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accidental = if self.alteration < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{:?}{}",
+            self.letter,
+            accidental.repeat(self.alteration.unsigned_abs() as usize)
+        )
+    }
+}
+
+impl FromStr for SpelledNote {
+    type Err = String;
+
+    /// Parses a note name such as "C", "f#" or "Bbb".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let letter = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some('A') => Letter::A,
+            Some('B') => Letter::B,
+            Some('C') => Letter::C,
+            Some('D') => Letter::D,
+            Some('E') => Letter::E,
+            Some('F') => Letter::F,
+            Some('G') => Letter::G,
+            _ => return Err(format!("\"{}\" is not a valid note", s)),
+        };
+        let alteration = chars.try_fold(0, |alteration, c| match c {
+            'b' => Ok(alteration - 1),
+            '#' => Ok(alteration + 1),
+            _ => Err(format!("\"{}\" is not a valid note", s)),
+        })?;
+        Ok(SpelledNote::new(letter, alteration))
+    }
+}
+
+/// Splits the note name at the start of `s`, e.g. "Bb" from "Bbm7", from the rest.
+pub fn split_note_name(s: &str) -> (&str, &str) {
+    let name_length = s
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c != 'b' && *c != '#')
+        .map_or(s.len(), |(index, _)| index);
+    s.split_at(name_length)
+}
+
+/// A scale degree such as b3 or #4: a degree number from 1 to 7, or up to 13 for
+/// chord extensions, and its alteration relative to the major scale.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Degree {
+    pub number: usize,
+    pub alteration: i32,
+}
+
+impl Degree {
+    pub const fn new(number: usize, alteration: i32) -> Self {
+        Degree { number, alteration }
+    }
+
+    /// Returns the number of semitones above the root, within an octave.
+    pub fn semitones(&self) -> usize {
+        let major_step = MAJOR_STEPS[(self.number - 1) % MAJOR_STEPS.len()];
+        (major_step as i32 + self.alteration).rem_euclid(NUM_NOTES as i32) as usize
+    }
+
+    /// Returns the interval name of the degree above the root, e.g. "m3" or "A4".
+    pub fn interval_name(&self) -> String {
+        let perfect = matches!((self.number - 1) % MAJOR_STEPS.len(), 0 | 3 | 4);
+        let quality = match (perfect, self.alteration) {
+            (true, 0) => String::from("P"),
+            (false, 0) => String::from("M"),
+            (false, -1) => String::from("m"),
+            (_, alteration) if alteration > 0 => "A".repeat(alteration as usize),
+            (true, alteration) => "d".repeat(alteration.unsigned_abs() as usize),
+            (false, alteration) => "d".repeat(alteration.unsigned_abs() as usize - 1),
+        };
+        format!("{}{}", quality, self.number)
+    }
+}
+
+impl fmt::Display for Degree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let accidental = if self.alteration < 0 { "b" } else { "#" };
+        write!(
+            f,
+            "{}{}",
+            accidental.repeat(self.alteration.unsigned_abs() as usize),
+            self.number
+        )
+    }
+}
+
+pub const fn accidental_to_spelled_note(accidental: &Accidental) -> SpelledNote {
+    match accidental {
+        Accidental::AFlat => SpelledNote::new(Letter::A, -1),
+        Accidental::A => SpelledNote::new(Letter::A, 0),
+        Accidental::ASharp => SpelledNote::new(Letter::A, 1),
+        Accidental::BFlat => SpelledNote::new(Letter::B, -1),
+        Accidental::B => SpelledNote::new(Letter::B, 0),
+        Accidental::C => SpelledNote::new(Letter::C, 0),
+        Accidental::CSharp => SpelledNote::new(Letter::C, 1),
+        Accidental::DFlat => SpelledNote::new(Letter::D, -1),
+        Accidental::D => SpelledNote::new(Letter::D, 0),
+        Accidental::DSharp => SpelledNote::new(Letter::D, 1),
+        Accidental::EFlat => SpelledNote::new(Letter::E, -1),
+        Accidental::E => SpelledNote::new(Letter::E, 0),
+        Accidental::F => SpelledNote::new(Letter::F, 0),
+        Accidental::FSharp => SpelledNote::new(Letter::F, 1),
+        Accidental::GFlat => SpelledNote::new(Letter::G, -1),
+        Accidental::G => SpelledNote::new(Letter::G, 0),
+        Accidental::GSharp => SpelledNote::new(Letter::G, 1),
+    }
+}
+
+/// Assigns a degree to every step of a scale. Seven note scales use each degree
+/// number exactly once; other scales fall back to the conventional name of each
+/// interval, choosing #4 over b5 when the 4th is missing and #5 over b6 when the
+/// 5th, the 6th and any other spelling of the 5th letter are missing.
+pub fn get_degrees_by_steps(steps: &[usize]) -> Vec<Degree> {
+    if steps.len() == MAJOR_STEPS.len() {
+        return steps
+            .iter()
+            .zip(MAJOR_STEPS)
+            .enumerate()
+            .map(|(degree_index, (step, major_step))| {
+                Degree::new(degree_index + 1, *step as i32 - major_step as i32)
+            })
+            .collect();
+    }
+    let mut degrees: Vec<Degree> = Vec::new();
+    for step in steps {
+        let degree = match step {
+            6 if !steps.contains(&5) => Degree::new(4, 1),
+            8 if !steps.contains(&9) && degrees.iter().all(|degree| degree.number != 5) => {
+                Degree::new(5, 1)
+            }
+            _ => {
+                let number = MAJOR_STEPS
+                    .iter()
+                    .position(|major_step| major_step >= step)
+                    .unwrap();
+                Degree::new(number + 1, *step as i32 - MAJOR_STEPS[number] as i32)
+            }
+        };
+        degrees.push(degree);
+    }
+    degrees
+}
+
+/// Spells every note of a scale built on `root`, giving each degree its own letter.
+pub fn spell_scale(root: SpelledNote, steps: &[usize]) -> Vec<SpelledNote> {
+    get_degrees_by_steps(steps)
+        .iter()
+        .zip(steps)
+        .map(|(degree, step)| {
+            let letter = root.letter_above(degree.number - 1);
+            let target_index = note_index(root.note()) + step;
+            let natural_index = note_index(letter_to_note(letter));
+            let alteration =
+                (target_index as i32 - natural_index as i32 + 6).rem_euclid(NUM_NOTES as i32) - 6;
+            SpelledNote::new(letter, alteration)
+        })
+        .collect()
+}
+
+/// Spells the note at a degree above `root`, e.g. F# for the #11 of C.
+pub fn spell_degree(root: SpelledNote, degree: Degree) -> SpelledNote {
+    let letter = root.letter_above(degree.number - 1);
+    let target_index = note_index(root.note()) + degree.semitones();
+    let natural_index = note_index(letter_to_note(letter));
+    let alteration =
+        (target_index as i32 - natural_index as i32 + 6).rem_euclid(NUM_NOTES as i32) - 6;
+    SpelledNote::new(letter, alteration)
+}
+
+/// Returns the degree of a spelled note `step` semitones above `root`, named after
+/// the distance between their letters.
+pub fn get_degree(root: SpelledNote, note: SpelledNote, step: usize) -> Degree {
+    let root_letter_index = LETTERS.iter().position(|&l| l == root.letter).unwrap();
+    let letter_index = LETTERS.iter().position(|&l| l == note.letter).unwrap();
+    let number = (letter_index + LETTERS.len() - root_letter_index) % LETTERS.len();
+    let alteration =
+        (step as i32 - MAJOR_STEPS[number] as i32 + 6).rem_euclid(NUM_NOTES as i32) - 6;
+    Degree::new(number + 1, alteration)
+}
+
+/// Picks the spelling of `root` that gives the scale the fewest accidentals,
+/// penalising double sharps and flats. Ties go to the sharp spelling.
+pub fn spell_root(root: Note, steps: &[usize]) -> SpelledNote {
+    let candidates = [1, 0, -1].map(|alteration| {
+        let natural_index = note_index(root) as i32 - alteration;
+        let natural = NOTES[natural_index.rem_euclid(NUM_NOTES as i32) as usize];
+        LETTERS
+            .iter()
+            .find(|&&letter| letter_to_note(letter) == natural)
+            .map(|&letter| SpelledNote::new(letter, alteration))
+    });
+    if let Some(natural) = candidates[1] {
+        return natural;
+    }
+    candidates
+        .iter()
+        .flatten()
+        .copied()
+        .min_by_key(|candidate| {
+            spell_scale(*candidate, steps)
+                .iter()
+                .map(|note| note.alteration * note.alteration)
+                .sum::<i32>()
+        })
+        .unwrap()
+}
+
+fn note_index(note: Note) -> usize {
+    NOTES.iter().position(|&n| n == note).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spell(root: SpelledNote, steps: &[usize]) -> Vec<String> {
+        spell_scale(root, steps)
+            .iter()
+            .map(|note| note.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_spell_scale() {
+        assert_eq!(
+            spell(SpelledNote::new(Letter::F, 1), &[0, 2, 4, 5, 7, 9, 11]),
+            vec!["F#", "G#", "A#", "B", "C#", "D#", "E#"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::G, -1), &[0, 2, 4, 5, 7, 9, 11]),
+            vec!["Gb", "Ab", "Bb", "Cb", "Db", "Eb", "F"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::G, 1), &[0, 2, 3, 5, 7, 8, 11]),
+            vec!["G#", "A#", "B", "C#", "D#", "E", "F##"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::C, 0), &[0, 1, 3, 4, 6, 8, 9]),
+            vec!["C", "Db", "Eb", "Fb", "Gb", "Ab", "Bbb"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::A, 0), &[0, 3, 5, 6, 7, 10]),
+            vec!["A", "C", "D", "Eb", "E", "G"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::C, 0), &[0, 2, 4, 6, 8, 10]),
+            vec!["C", "D", "E", "F#", "G#", "Bb"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::C, 1), &[0, 2, 3, 5, 6, 8, 9, 11]),
+            vec!["C#", "D#", "E", "F#", "G", "A", "A#", "B#"]
+        );
+        assert_eq!(
+            spell(SpelledNote::new(Letter::C, 0), &[0, 1, 3, 4, 6, 7, 9, 10]),
+            vec!["C", "Db", "Eb", "E", "F#", "G", "A", "Bb"]
+        );
+    }
+
+    #[test]
+    fn test_get_degree() {
+        let root = SpelledNote::new(Letter::C, 0);
+        let degrees = spell_scale(root, &[0, 1, 3, 4, 6, 8, 9])
+            .iter()
+            .zip([0, 1, 3, 4, 6, 8, 9])
+            .map(|(note, step)| get_degree(root, *note, step))
+            .collect::<Vec<Degree>>();
+        assert_eq!(
+            degrees.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+            vec!["1", "b2", "b3", "b4", "b5", "b6", "bb7"]
+        );
+        assert_eq!(
+            degrees
+                .iter()
+                .map(|d| d.interval_name())
+                .collect::<Vec<_>>(),
+            vec!["P1", "m2", "m3", "d4", "d5", "m6", "d7"]
+        );
+        assert_eq!(Degree::new(4, 1).interval_name(), "A4");
+        assert_eq!(Degree::new(11, 1).interval_name(), "A11");
+        assert_eq!(Degree::new(13, -1).interval_name(), "m13");
+        assert_eq!(Degree::new(2, 2).to_string(), "##2");
+        assert_eq!(Degree::new(9, -1).semitones(), 1);
+        assert_eq!(Degree::new(7, -2).semitones(), 9);
+    }
+
+    #[test]
+    fn test_spell_degree() {
+        let root = SpelledNote::new(Letter::C, 0);
+        assert_eq!(
+            spell_degree(root, Degree::new(11, 1)),
+            SpelledNote::new(Letter::F, 1)
+        );
+        assert_eq!(
+            spell_degree(SpelledNote::new(Letter::B, -1), Degree::new(13, 0)),
+            SpelledNote::new(Letter::G, 0)
+        );
+        assert_eq!(
+            spell_degree(SpelledNote::new(Letter::F, 1), Degree::new(5, -1)),
+            SpelledNote::new(Letter::C, 0)
+        );
+    }
+
+    #[test]
+    fn test_parse_spelled_note() {
+        assert_eq!("Bb".parse(), Ok(SpelledNote::new(Letter::B, -1)));
+        assert_eq!("f##".parse(), Ok(SpelledNote::new(Letter::F, 2)));
+        assert!("H".parse::<SpelledNote>().is_err());
+        assert!("C#x".parse::<SpelledNote>().is_err());
+        assert_eq!(split_note_name("Bbm7b5"), ("Bb", "m7b5"));
+        assert_eq!(split_note_name("F#"), ("F#", ""));
+    }
+
+    #[test]
+    fn test_spell_root() {
+        assert_eq!(
+            spell_root(Note::DSharp, &[0, 2, 3, 5, 7, 8, 11]),
+            SpelledNote::new(Letter::E, -1)
+        );
+        assert_eq!(
+            spell_root(Note::CSharp, &[0, 2, 3, 5, 7, 8, 10]),
+            SpelledNote::new(Letter::C, 1)
+        );
+        assert_eq!(
+            spell_root(Note::B, &[0, 2, 4, 5, 7, 9, 11]),
+            SpelledNote::new(Letter::B, 0)
+        );
+        assert_eq!(SpelledNote::new(Letter::B, 1).note(), Note::C);
+    }
+}
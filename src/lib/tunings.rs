@@ -1,7 +1,13 @@
-use super::notes::Note;
+use super::instruments::{
+    get_lowest_pitch_without_octave, get_num_frets, get_num_thin_strings, instrument_to_string,
+    Instrument,
+};
+use super::notes::{Note, NUM_NOTES};
+use super::pitches::Pitch;
+use super::spelling::{split_note_name, SpelledNote};
 use clap::ValueEnum;
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
 pub enum Tuning {
     StandardE6,
     OpenG6,
@@ -19,6 +25,23 @@ pub enum Tuning {
     DropA7,
     StandardA7,
     AllFourths7,
+    StandardFSharp8,
+    DropE8,
+    StandardCSharp9,
+    DropB9,
+    BassStandardE4,
+    BassDropD4,
+    BassStandardB5,
+    BassDropA5,
+    BassStandardE5,
+    BassStandardB6,
+    UkuleleSoprano4,
+    UkuleleTenor4,
+    UkuleleBaritone4,
+    Mandolin4,
+    Mandola4,
+    BanjoOpenG5,
+    BanjoDoubleC5,
 }
 
 pub const fn tuning_to_string(tuning: Tuning) -> &'static str {
@@ -39,81 +62,514 @@ pub const fn tuning_to_string(tuning: Tuning) -> &'static str {
         Tuning::DropA7 => "Drop A (7 string)",
         Tuning::StandardA7 => "Standard A (7 string)",
         Tuning::AllFourths7 => "All fourths (7 string)",
+        Tuning::StandardFSharp8 => "Standard F# (8 string)",
+        Tuning::DropE8 => "Drop E (8 string)",
+        Tuning::StandardCSharp9 => "Standard C# (9 string)",
+        Tuning::DropB9 => "Drop B (9 string)",
+        Tuning::BassStandardE4 => "Standard E (4 string bass)",
+        Tuning::BassDropD4 => "Drop D (4 string bass)",
+        Tuning::BassStandardB5 => "Standard B (5 string bass)",
+        Tuning::BassDropA5 => "Drop A (5 string bass)",
+        Tuning::BassStandardE5 => "Standard E with high C (5 string bass)",
+        Tuning::BassStandardB6 => "Standard B (6 string bass)",
+        Tuning::UkuleleSoprano4 => "Soprano with high G (4 string ukulele)",
+        Tuning::UkuleleTenor4 => "Tenor with low G (4 string ukulele)",
+        Tuning::UkuleleBaritone4 => "Baritone (4 string ukulele)",
+        Tuning::Mandolin4 => "Mandolin (4 course)",
+        Tuning::Mandola4 => "Mandola (4 course)",
+        Tuning::BanjoOpenG5 => "Open G (5 string banjo)",
+        Tuning::BanjoDoubleC5 => "Double C (5 string banjo)",
     }
 }
 
-pub const fn get_notes_by_tuning(tuning: Tuning) -> &'static [Note] {
+const STANDARD_E6: [Pitch; 6] = [
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const OPEN_G6: [Pitch; 6] = [
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::D, 4),
+];
+
+const OPEN_E6: [Pitch; 6] = [
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::B, 2),
+    Pitch::new(Note::E, 3),
+    Pitch::new(Note::GSharp, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const OPEN_D6: [Pitch; 6] = [
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::FSharp, 3),
+    Pitch::new(Note::A, 3),
+    Pitch::new(Note::D, 4),
+];
+
+const OPEN_C6: [Pitch; 6] = [
+    Pitch::new(Note::C, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::C, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::C, 4),
+    Pitch::new(Note::E, 4),
+];
+
+const OPEN_A6: [Pitch; 6] = [
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::E, 3),
+    Pitch::new(Note::A, 3),
+    Pitch::new(Note::CSharp, 4),
+    Pitch::new(Note::E, 4),
+];
+
+const DROP_D6: [Pitch; 6] = [
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const STANDARD_D6: [Pitch; 6] = [
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::C, 3),
+    Pitch::new(Note::F, 3),
+    Pitch::new(Note::A, 3),
+    Pitch::new(Note::D, 4),
+];
+
+const DROP_C_SHARP6: [Pitch; 6] = [
+    Pitch::new(Note::CSharp, 2),
+    Pitch::new(Note::GSharp, 2),
+    Pitch::new(Note::CSharp, 3),
+    Pitch::new(Note::FSharp, 3),
+    Pitch::new(Note::ASharp, 3),
+    Pitch::new(Note::DSharp, 4),
+];
+
+const STANDARD_C_SHARP6: [Pitch; 6] = [
+    Pitch::new(Note::CSharp, 2),
+    Pitch::new(Note::FSharp, 2),
+    Pitch::new(Note::CSharp, 3),
+    Pitch::new(Note::E, 3),
+    Pitch::new(Note::GSharp, 3),
+    Pitch::new(Note::CSharp, 4),
+];
+
+const DROP_C6: [Pitch; 6] = [
+    Pitch::new(Note::C, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::C, 3),
+    Pitch::new(Note::F, 3),
+    Pitch::new(Note::A, 3),
+    Pitch::new(Note::D, 4),
+];
+
+const STANDARD_C6: [Pitch; 6] = [
+    Pitch::new(Note::C, 2),
+    Pitch::new(Note::F, 2),
+    Pitch::new(Note::ASharp, 2),
+    Pitch::new(Note::DSharp, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::C, 4),
+];
+
+const STANDARD_B7: [Pitch; 7] = [
+    Pitch::new(Note::B, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const DROP_A7: [Pitch; 7] = [
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const STANDARD_A7: [Pitch; 7] = [
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::C, 3),
+    Pitch::new(Note::F, 3),
+    Pitch::new(Note::A, 3),
+    Pitch::new(Note::D, 4),
+];
+
+const ALL_FOURTHS_7: [Pitch; 7] = [
+    Pitch::new(Note::B, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::C, 4),
+    Pitch::new(Note::F, 4),
+];
+
+const STANDARD_F_SHARP8: [Pitch; 8] = [
+    Pitch::new(Note::FSharp, 1),
+    Pitch::new(Note::B, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const DROP_E8: [Pitch; 8] = [
+    Pitch::new(Note::E, 1),
+    Pitch::new(Note::B, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const STANDARD_C_SHARP9: [Pitch; 9] = [
+    Pitch::new(Note::CSharp, 1),
+    Pitch::new(Note::FSharp, 1),
+    Pitch::new(Note::B, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const DROP_B9: [Pitch; 9] = [
+    Pitch::new(Note::B, 0),
+    Pitch::new(Note::FSharp, 1),
+    Pitch::new(Note::B, 1),
+    Pitch::new(Note::E, 2),
+    Pitch::new(Note::A, 2),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+const BASS_STANDARD_E4: [Pitch; 4] = [
+    Pitch::new(Note::E, 1),
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+];
+
+const BASS_DROP_D4: [Pitch; 4] = [
+    Pitch::new(Note::D, 1),
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+];
+
+const BASS_STANDARD_B5: [Pitch; 5] = [
+    Pitch::new(Note::B, 0),
+    Pitch::new(Note::E, 1),
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+];
+
+const BASS_DROP_A5: [Pitch; 5] = [
+    Pitch::new(Note::A, 0),
+    Pitch::new(Note::E, 1),
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+];
+
+const BASS_STANDARD_E5: [Pitch; 5] = [
+    Pitch::new(Note::E, 1),
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::C, 3),
+];
+
+const BASS_STANDARD_B6: [Pitch; 6] = [
+    Pitch::new(Note::B, 0),
+    Pitch::new(Note::E, 1),
+    Pitch::new(Note::A, 1),
+    Pitch::new(Note::D, 2),
+    Pitch::new(Note::G, 2),
+    Pitch::new(Note::C, 3),
+];
+
+const UKULELE_SOPRANO4: [Pitch; 4] = [
+    Pitch::new(Note::G, 4),
+    Pitch::new(Note::C, 4),
+    Pitch::new(Note::E, 4),
+    Pitch::new(Note::A, 4),
+];
+
+const UKULELE_TENOR4: [Pitch; 4] = [
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::C, 4),
+    Pitch::new(Note::E, 4),
+    Pitch::new(Note::A, 4),
+];
+
+const UKULELE_BARITONE4: [Pitch; 4] = [
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::E, 4),
+];
+
+// The strings of each course are tuned in unison and shown as one string.
+const MANDOLIN4: [Pitch; 4] = [
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::D, 4),
+    Pitch::new(Note::A, 4),
+    Pitch::new(Note::E, 5),
+];
+
+const MANDOLA4: [Pitch; 4] = [
+    Pitch::new(Note::C, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::D, 4),
+    Pitch::new(Note::A, 4),
+];
+
+// The short fifth string comes first, as it lies above the fourth string.
+const BANJO_OPEN_G5: [Pitch; 5] = [
+    Pitch::new(Note::G, 4),
+    Pitch::new(Note::D, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::B, 3),
+    Pitch::new(Note::D, 4),
+];
+
+const BANJO_DOUBLE_C5: [Pitch; 5] = [
+    Pitch::new(Note::G, 4),
+    Pitch::new(Note::C, 3),
+    Pitch::new(Note::G, 3),
+    Pitch::new(Note::C, 4),
+    Pitch::new(Note::D, 4),
+];
+
+/// The fret at which the fifth string of a banjo comes out of the neck.
+const BANJO_FIRST_FRETS: [usize; 5] = [5, 0, 0, 0, 0];
+
+pub const fn get_pitches_by_tuning(tuning: Tuning) -> &'static [Pitch] {
+    match tuning {
+        Tuning::StandardE6 => &STANDARD_E6,
+        Tuning::OpenG6 => &OPEN_G6,
+        Tuning::OpenE6 => &OPEN_E6,
+        Tuning::OpenD6 => &OPEN_D6,
+        Tuning::OpenC6 => &OPEN_C6,
+        Tuning::OpenA6 => &OPEN_A6,
+        Tuning::DropD6 => &DROP_D6,
+        Tuning::StandardD6 => &STANDARD_D6,
+        Tuning::DropCSharp6 => &DROP_C_SHARP6,
+        Tuning::StandardCSharp6 => &STANDARD_C_SHARP6,
+        Tuning::DropC6 => &DROP_C6,
+        Tuning::StandardC6 => &STANDARD_C6,
+        Tuning::StandardB7 => &STANDARD_B7,
+        Tuning::DropA7 => &DROP_A7,
+        Tuning::StandardA7 => &STANDARD_A7,
+        Tuning::AllFourths7 => &ALL_FOURTHS_7,
+        Tuning::StandardFSharp8 => &STANDARD_F_SHARP8,
+        Tuning::DropE8 => &DROP_E8,
+        Tuning::StandardCSharp9 => &STANDARD_C_SHARP9,
+        Tuning::DropB9 => &DROP_B9,
+        Tuning::BassStandardE4 => &BASS_STANDARD_E4,
+        Tuning::BassDropD4 => &BASS_DROP_D4,
+        Tuning::BassStandardB5 => &BASS_STANDARD_B5,
+        Tuning::BassDropA5 => &BASS_DROP_A5,
+        Tuning::BassStandardE5 => &BASS_STANDARD_E5,
+        Tuning::BassStandardB6 => &BASS_STANDARD_B6,
+        Tuning::UkuleleSoprano4 => &UKULELE_SOPRANO4,
+        Tuning::UkuleleTenor4 => &UKULELE_TENOR4,
+        Tuning::UkuleleBaritone4 => &UKULELE_BARITONE4,
+        Tuning::Mandolin4 => &MANDOLIN4,
+        Tuning::Mandola4 => &MANDOLA4,
+        Tuning::BanjoOpenG5 => &BANJO_OPEN_G5,
+        Tuning::BanjoDoubleC5 => &BANJO_DOUBLE_C5,
+    }
+}
+
+/// Returns the fret each string starts at, for tunings with strings that do not
+/// start at the nut. The pitch of such a string is the one played at that fret.
+pub const fn get_first_frets_by_tuning(tuning: Tuning) -> Option<&'static [usize]> {
     match tuning {
-        Tuning::StandardE6 => &[Note::E, Note::A, Note::D, Note::G, Note::B, Note::E],
-        Tuning::OpenG6 => &[Note::D, Note::G, Note::D, Note::G, Note::B, Note::D],
-        Tuning::OpenE6 => &[Note::E, Note::B, Note::E, Note::GSharp, Note::B, Note::E],
-        Tuning::OpenD6 => &[Note::D, Note::A, Note::D, Note::FSharp, Note::A, Note::D],
-        Tuning::OpenC6 => &[Note::C, Note::G, Note::C, Note::G, Note::C, Note::E],
-        Tuning::OpenA6 => &[Note::E, Note::A, Note::E, Note::A, Note::CSharp, Note::E],
-        Tuning::DropD6 => &[Note::D, Note::A, Note::D, Note::G, Note::B, Note::E],
-        Tuning::StandardD6 => &[Note::D, Note::G, Note::C, Note::F, Note::A, Note::D],
-        Tuning::DropCSharp6 => &[
-            Note::CSharp,
-            Note::GSharp,
-            Note::CSharp,
-            Note::FSharp,
-            Note::ASharp,
-            Note::DSharp,
-        ],
-        Tuning::StandardCSharp6 => &[
-            Note::CSharp,
-            Note::FSharp,
-            Note::CSharp,
-            Note::E,
-            Note::GSharp,
-            Note::CSharp,
-        ],
-        Tuning::DropC6 => &[Note::C, Note::G, Note::C, Note::F, Note::A, Note::D],
-        Tuning::StandardC6 => &[
-            Note::C,
-            Note::F,
-            Note::ASharp,
-            Note::DSharp,
-            Note::G,
-            Note::C,
-        ],
-        Tuning::StandardB7 => &[
-            Note::B,
-            Note::E,
-            Note::A,
-            Note::D,
-            Note::G,
-            Note::B,
-            Note::E,
-        ],
-        Tuning::DropA7 => &[
-            Note::A,
-            Note::E,
-            Note::A,
-            Note::D,
-            Note::G,
-            Note::B,
-            Note::E,
-        ],
-        Tuning::StandardA7 => &[
-            Note::A,
-            Note::D,
-            Note::G,
-            Note::C,
-            Note::F,
-            Note::A,
-            Note::D,
-        ],
-        Tuning::AllFourths7 => &[
-            Note::B,
-            Note::E,
-            Note::A,
-            Note::D,
-            Note::G,
-            Note::C,
-            Note::F,
-        ],
+        Tuning::BanjoOpenG5 | Tuning::BanjoDoubleC5 => Some(&BANJO_FIRST_FRETS),
+        _ => None,
+    }
+}
+
+pub const fn get_instrument_by_tuning(tuning: Tuning) -> Instrument {
+    match tuning {
+        Tuning::BassStandardE4
+        | Tuning::BassDropD4
+        | Tuning::BassStandardB5
+        | Tuning::BassDropA5
+        | Tuning::BassStandardE5
+        | Tuning::BassStandardB6 => Instrument::Bass,
+        Tuning::UkuleleSoprano4 | Tuning::UkuleleTenor4 | Tuning::UkuleleBaritone4 => {
+            Instrument::Ukulele
+        }
+        Tuning::Mandolin4 | Tuning::Mandola4 => Instrument::Mandolin,
+        Tuning::BanjoOpenG5 | Tuning::BanjoDoubleC5 => Instrument::Banjo,
+        _ => Instrument::Guitar,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomTuning {
+    pub name: String,
+    /// The open strings, from the thickest.
+    pub pitches: Vec<Pitch>,
+    /// The instrument the tuning is played on, guitar unless declared otherwise.
+    pub instrument: Instrument,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TuningChoice {
+    BuiltIn(Tuning),
+    Custom(CustomTuning),
+}
+
+impl TuningChoice {
+    pub fn pitches(&self) -> &[Pitch] {
+        match self {
+            TuningChoice::BuiltIn(tuning) => get_pitches_by_tuning(*tuning),
+            TuningChoice::Custom(custom_tuning) => &custom_tuning.pitches,
+        }
+    }
+
+    pub fn instrument(&self) -> Instrument {
+        match self {
+            TuningChoice::BuiltIn(tuning) => get_instrument_by_tuning(*tuning),
+            TuningChoice::Custom(custom_tuning) => custom_tuning.instrument,
+        }
     }
+
+    /// Returns the fret each string starts at, counting strings from the thickest.
+    pub fn first_frets(&self) -> Vec<usize> {
+        match self {
+            TuningChoice::BuiltIn(tuning) => get_first_frets_by_tuning(*tuning)
+                .map(|first_frets| first_frets.to_vec())
+                .unwrap_or_else(|| vec![0; self.pitches().len()]),
+            TuningChoice::Custom(custom_tuning) => vec![0; custom_tuning.pitches.len()],
+        }
+    }
+
+    pub fn num_frets(&self) -> usize {
+        get_num_frets(self.instrument())
+    }
+
+    /// Returns whether a string, counting from the thickest, is wound and drawn
+    /// thick. The highest strings and the strings that do not start at the nut are
+    /// plain.
+    pub fn is_thick_string(&self, string: usize) -> bool {
+        let num_thick_strings = self
+            .pitches()
+            .len()
+            .saturating_sub(get_num_thin_strings(self.instrument()));
+        string < num_thick_strings && self.first_frets()[string] == 0
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            TuningChoice::BuiltIn(tuning) => String::from(tuning_to_string(*tuning)),
+            TuningChoice::Custom(custom_tuning) => {
+                let num_strings = custom_tuning.pitches.len();
+                let strings = match custom_tuning.instrument {
+                    Instrument::Guitar => format!("{} string", num_strings),
+                    Instrument::Mandolin => format!("{} course", num_strings),
+                    instrument => format!(
+                        "{} string {}",
+                        num_strings,
+                        instrument_to_string(instrument).to_lowercase()
+                    ),
+                };
+                format!("{} ({})", custom_tuning.name, strings)
+            }
+        }
+    }
+}
+
+/// Returns the pitch each string sounds at its first fret with a capo behind
+/// `capo_fret`, or without a capo if it is 0. A string that starts past the capo
+/// keeps its pitch.
+pub fn get_first_fret_pitches(tuning: &TuningChoice, capo_fret: usize) -> Vec<Pitch> {
+    tuning
+        .pitches()
+        .iter()
+        .zip(tuning.first_frets())
+        .map(|(pitch, first_fret)| pitch.transpose(capo_fret.saturating_sub(first_fret) as i32))
+        .collect()
+}
+
+/// Parses the open strings of a tuning on an instrument from the thickest, separated
+/// by spaces or commas, e.g. "E2 A2 D3 G3 B3 E4". A note without an octave is the
+/// lowest one above the previous string, so "D A D G A D" is D2 A2 D3 G3 A3 D4 on
+/// guitar and "E A D G" is E1 A1 D2 G2 on bass.
+pub fn parse_tuning_notes(notes: &str, instrument: Instrument) -> Result<Vec<Pitch>, String> {
+    let mut pitches: Vec<Pitch> = Vec::new();
+    for name in notes.split([' ', ',']).filter(|name| !name.is_empty()) {
+        let (_, octave) = split_note_name(name);
+        let pitch = if octave.is_empty() {
+            let note = name.parse::<SpelledNote>()?.note();
+            let lowest_midi_number = pitches.last().map_or(
+                get_lowest_pitch_without_octave(instrument).midi_number(),
+                |previous| previous.midi_number() + 1,
+            );
+            let semitones = (Pitch::new(note, 0).midi_number() - lowest_midi_number)
+                .rem_euclid(NUM_NOTES as i32);
+            Pitch::from_midi_number(lowest_midi_number + semitones)
+        } else {
+            name.parse::<Pitch>()?
+        };
+        pitches.push(pitch);
+    }
+    if pitches.is_empty() {
+        return Err(String::from("No strings were provided"));
+    }
+    Ok(pitches)
+}
+
+pub fn parse_custom_tuning(
+    name: &str,
+    notes: &str,
+    instrument: Instrument,
+) -> Result<CustomTuning, String> {
+    let pitches = parse_tuning_notes(notes, instrument)
+        .map_err(|err| format!("Invalid notes for tuning \"{}\": {}", name, err))?;
+    Ok(CustomTuning {
+        name: String::from(name),
+        pitches,
+        instrument,
+    })
 }
 
 #[cfg(test)]
@@ -124,12 +580,64 @@ mod test {
     fn test_get_tuning_name() {
         assert_eq!(tuning_to_string(Tuning::OpenG6), "Open G (6 string)");
         assert_eq!(tuning_to_string(Tuning::DropA7), "Drop A (7 string)");
+        assert_eq!(
+            tuning_to_string(Tuning::BassStandardB5),
+            "Standard B (5 string bass)"
+        );
+        assert_eq!(
+            tuning_to_string(Tuning::StandardFSharp8),
+            "Standard F# (8 string)"
+        );
+        assert_eq!(tuning_to_string(Tuning::DropB9), "Drop B (9 string)");
+        for tuning in Tuning::value_variants() {
+            let num_strings = get_pitches_by_tuning(*tuning).len().to_string();
+            assert!(
+                tuning_to_string(*tuning).contains(&format!("({} string", num_strings))
+                    || tuning_to_string(*tuning).contains(&format!("({} course", num_strings)),
+                "{:?}",
+                tuning
+            );
+        }
     }
 
     #[test]
-    fn test_get_notes_by_tuning() {
+    fn test_get_pitches_by_tuning() {
         assert_eq!(
-            get_notes_by_tuning(Tuning::StandardCSharp6),
+            get_pitches_by_tuning(Tuning::StandardE6)
+                .iter()
+                .map(Pitch::to_string)
+                .collect::<Vec<String>>(),
+            vec!["E2", "A2", "D3", "G3", "B3", "E4"]
+        );
+        for tuning in Tuning::value_variants() {
+            let pitches = get_pitches_by_tuning(*tuning);
+            let octaves = match get_instrument_by_tuning(*tuning) {
+                Instrument::Guitar => 0..=4,
+                Instrument::Bass => 0..=3,
+                Instrument::Ukulele | Instrument::Banjo => 3..=4,
+                Instrument::Mandolin => 3..=5,
+            };
+            if let Some(first_frets) = get_first_frets_by_tuning(*tuning) {
+                assert_eq!(first_frets.len(), pitches.len(), "{:?}", tuning);
+            }
+            assert!(
+                pitches.iter().all(|pitch| octaves.contains(&pitch.octave)),
+                "{:?}",
+                tuning
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_first_fret_pitches() {
+        let get_notes_by_tuning = |tuning: &TuningChoice, capo_fret| {
+            get_first_fret_pitches(tuning, capo_fret)
+                .iter()
+                .map(|pitch| pitch.note)
+                .collect::<Vec<Note>>()
+        };
+        assert_eq!(
+            get_notes_by_tuning(&TuningChoice::BuiltIn(Tuning::StandardCSharp6), 0),
             &[
                 Note::CSharp,
                 Note::FSharp,
@@ -140,7 +648,7 @@ mod test {
             ]
         );
         assert_eq!(
-            get_notes_by_tuning(Tuning::DropA7),
+            get_notes_by_tuning(&TuningChoice::BuiltIn(Tuning::DropA7), 0),
             &[
                 Note::A,
                 Note::E,
@@ -151,5 +659,88 @@ mod test {
                 Note::E
             ]
         );
+        assert_eq!(
+            get_notes_by_tuning(&TuningChoice::BuiltIn(Tuning::BanjoOpenG5), 2),
+            &[Note::G, Note::E, Note::A, Note::CSharp, Note::E]
+        );
+        assert_eq!(
+            get_first_fret_pitches(&TuningChoice::BuiltIn(Tuning::BanjoOpenG5), 2)[..2],
+            [Pitch::new(Note::G, 4), Pitch::new(Note::E, 3)]
+        );
+    }
+
+    #[test]
+    fn test_parse_tuning_notes() {
+        let pitch_names = |notes: &str| {
+            parse_tuning_notes(notes, Instrument::Guitar).map(|pitches| {
+                pitches
+                    .iter()
+                    .map(Pitch::to_string)
+                    .collect::<Vec<String>>()
+            })
+        };
+        assert_eq!(
+            pitch_names("D A D G A D"),
+            Ok(vec!["D2", "A2", "D3", "G3", "A3", "D4"]
+                .into_iter()
+                .map(String::from)
+                .collect())
+        );
+        assert_eq!(
+            pitch_names("B, E, A, D, G, B, E").unwrap()[..2],
+            ["B1", "E2"]
+        );
+        assert_eq!(pitch_names("E3 A3 D4 G4 B3 E4").unwrap()[4], "B3");
+        assert_eq!(pitch_names("G4 C4 E A").unwrap(), ["G4", "C4", "E4", "A4"]);
+        assert!(pitch_names("").is_err());
+        assert!(pitch_names("D A H").is_err());
+
+        let tuning = TuningChoice::Custom(
+            parse_custom_tuning("DADGAD", "D A D G A D", Instrument::Guitar).unwrap(),
+        );
+        assert_eq!(tuning.name(), "DADGAD (6 string)");
+        assert_eq!(
+            get_first_fret_pitches(&tuning, 0)[2],
+            Pitch::new(Note::D, 3)
+        );
+        assert_eq!(tuning.instrument(), Instrument::Guitar);
+        assert!(tuning.is_thick_string(2));
+        assert!(!tuning.is_thick_string(3));
+
+        let tuning = TuningChoice::Custom(
+            parse_custom_tuning("Tenor", "A1 D2 G2 C3", Instrument::Bass).unwrap(),
+        );
+        assert_eq!(tuning.instrument(), Instrument::Bass);
+        assert_eq!(tuning.name(), "Tenor (4 string bass)");
+        assert!(tuning.is_thick_string(3));
+        assert_eq!(
+            parse_tuning_notes("E A D G", Instrument::Bass).unwrap(),
+            [
+                Pitch::new(Note::E, 1),
+                Pitch::new(Note::A, 1),
+                Pitch::new(Note::D, 2),
+                Pitch::new(Note::G, 2)
+            ]
+        );
+        let tuning = TuningChoice::Custom(
+            parse_custom_tuning("E A D G", "E A D G", Instrument::Guitar).unwrap(),
+        );
+        assert_eq!(tuning.instrument(), Instrument::Guitar);
+        assert!(!tuning.is_thick_string(3));
+        assert_eq!(
+            parse_tuning_notes("G C E A", Instrument::Ukulele).unwrap()[0],
+            Pitch::new(Note::G, 3)
+        );
+        assert!(TuningChoice::BuiltIn(Tuning::BassStandardB5).is_thick_string(4));
+
+        let banjo = TuningChoice::BuiltIn(Tuning::BanjoOpenG5);
+        assert_eq!(banjo.first_frets(), [5, 0, 0, 0, 0]);
+        assert_eq!(banjo.num_frets(), 22);
+        assert_eq!(
+            (0..5)
+                .map(|string| banjo.is_thick_string(string))
+                .collect::<Vec<bool>>(),
+            [false, true, false, false, false]
+        );
     }
 }
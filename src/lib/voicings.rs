@@ -0,0 +1,419 @@
+use std::cmp::Reverse;
+
+use super::fret_board::FRET_SPAN;
+use super::notes::Note;
+use super::pitch_classes::PitchClassSet;
+use super::pitches::Pitch;
+
+/// The largest distance between the lowest and highest fretted note of a voicing,
+/// unless another one is given.
+pub const DEFAULT_MAX_STRETCH: usize = 3;
+
+/// The largest number of voicings printed for a chord.
+pub const MAX_VOICINGS: usize = 6;
+
+const NUM_FINGERS: usize = 4;
+
+/// A way to play a chord, with the fret played on each string from the thickest to
+/// the thinnest, or `None` if the string is muted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Voicing {
+    pub frets: Vec<Option<usize>>,
+}
+
+impl Voicing {
+    fn num_played_strings(&self) -> usize {
+        self.frets.iter().flatten().count()
+    }
+
+    fn num_inner_muted_strings(&self) -> usize {
+        get_num_inner_muted_strings(&self.frets)
+    }
+
+    fn stretch(&self) -> usize {
+        get_stretch(&self.frets)
+    }
+
+    /// Voicings that play more strings come first, then those with fewer muted
+    /// strings in between, a smaller stretch and lower frets.
+    fn rank(&self) -> (Reverse<usize>, usize, usize, usize) {
+        (
+            Reverse(self.num_played_strings()),
+            self.num_inner_muted_strings(),
+            self.stretch(),
+            self.frets.iter().flatten().sum::<usize>(),
+        )
+    }
+
+    /// Returns the fingering in tab notation, e.g. "x02010", separating frets with
+    /// dashes once any fret has two digits.
+    pub fn fingering(&self) -> String {
+        let frets = self
+            .frets
+            .iter()
+            .map(|fret| fret.map_or(String::from("x"), |fret| fret.to_string()))
+            .collect::<Vec<String>>();
+        if frets.iter().any(|fret| fret.len() > 1) {
+            frets.join("-")
+        } else {
+            frets.concat()
+        }
+    }
+}
+
+/// Returns the number of muted strings between the lowest and highest played
+/// strings.
+fn get_num_inner_muted_strings(frets: &[Option<usize>]) -> usize {
+    let first = frets.iter().position(Option::is_some);
+    let last = frets.iter().rposition(Option::is_some);
+    match (first, last) {
+        (Some(first), Some(last)) => frets[first..=last]
+            .iter()
+            .filter(|fret| fret.is_none())
+            .count(),
+        _ => 0,
+    }
+}
+
+/// Returns the distance between the lowest and highest fretted note of some strings.
+fn get_stretch(frets: &[Option<usize>]) -> usize {
+    let fretted = frets.iter().flatten().filter(|fret| **fret > 0);
+    match (fretted.clone().min(), fretted.max()) {
+        (Some(min), Some(max)) => max - min,
+        _ => 0,
+    }
+}
+
+/// Returns the number of fingers needed to fret some strings. The index finger can
+/// barre the lowest fret, every other fretted note takes a finger of its own.
+fn get_num_fingers(frets: &[Option<usize>]) -> usize {
+    let fretted = frets.iter().flatten().filter(|fret| **fret > 0);
+    match fretted.clone().min() {
+        Some(min) => 1 + fretted.filter(|fret| *fret > min).count(),
+        None => 0,
+    }
+}
+
+/// Finds the best playable voicings of a chord, at most `MAX_VOICINGS`, on the
+/// strings of a tuning within the window of frets starting at `starting_fret`,
+/// where each string sounds its pitch at its first fret. Every chord tone must be
+/// played, the lowest pitch played must be `bass`, whichever string it is on, at
+/// most one string between the played strings may be muted and the fretted notes
+/// must fit in one hand, at most `max_stretch` frets apart.
+pub fn find_voicings(
+    strings: &[Pitch],
+    first_frets: &[usize],
+    starting_fret: usize,
+    chord_notes: &[Note],
+    bass: Note,
+    max_stretch: usize,
+) -> Vec<Voicing> {
+    let options_by_string = strings
+        .iter()
+        .zip(first_frets)
+        .map(|(string, first_fret)| {
+            (starting_fret.max(*first_fret)..starting_fret + FRET_SPAN)
+                .map(|fret| (fret, string.transpose((fret - first_fret) as i32)))
+                .filter(|(_, pitch)| chord_notes.contains(&pitch.note))
+                .collect::<Vec<(usize, Pitch)>>()
+        })
+        .collect::<Vec<Vec<(usize, Pitch)>>>();
+    let mut remaining_pitch_classes = vec![PitchClassSet::from_notes(&[])];
+    let mut remaining_lowest_midi_numbers = vec![i32::MAX];
+    for options in options_by_string.iter().rev() {
+        let notes = options
+            .iter()
+            .map(|(_, pitch)| pitch.note)
+            .collect::<Vec<Note>>();
+        let lowest_midi_number = options.iter().map(|(_, pitch)| pitch.midi_number()).min();
+        remaining_pitch_classes.push(
+            PitchClassSet::from_notes(&notes).union(*remaining_pitch_classes.last().unwrap()),
+        );
+        remaining_lowest_midi_numbers.push(
+            lowest_midi_number
+                .unwrap_or(i32::MAX)
+                .min(*remaining_lowest_midi_numbers.last().unwrap()),
+        );
+    }
+    remaining_pitch_classes.reverse();
+    remaining_lowest_midi_numbers.reverse();
+
+    let search = VoicingSearch {
+        options_by_string,
+        remaining_pitch_classes,
+        remaining_lowest_midi_numbers,
+        chord_pitch_classes: PitchClassSet::from_notes(chord_notes),
+        bass,
+        max_stretch,
+    };
+    let mut voicings = Vec::new();
+    search.search(&mut Vec::new(), &mut Vec::new(), &mut voicings);
+    voicings
+}
+
+/// The options of each string for a chord, with what the strings from each one on
+/// can still add, so that the search drops a combination as soon as it cannot
+/// become a playable voicing.
+struct VoicingSearch {
+    /// The frets each string can play a chord tone at, with the pitch it sounds.
+    options_by_string: Vec<Vec<(usize, Pitch)>>,
+    /// The chord tones the strings from each one on can play.
+    remaining_pitch_classes: Vec<PitchClassSet>,
+    /// The lowest MIDI number the strings from each one on can play.
+    remaining_lowest_midi_numbers: Vec<i32>,
+    chord_pitch_classes: PitchClassSet,
+    bass: Note,
+    max_stretch: usize,
+}
+
+impl VoicingSearch {
+    /// Walks the options for each string, muting it or playing one of its frets,
+    /// and keeps the best voicings found.
+    fn search(
+        &self,
+        frets: &mut Vec<Option<usize>>,
+        played_pitches: &mut Vec<Pitch>,
+        voicings: &mut Vec<Voicing>,
+    ) {
+        if !self.can_complete(frets, played_pitches) {
+            return;
+        }
+        let Some(options) = self.options_by_string.get(frets.len()) else {
+            insert_voicing(
+                voicings,
+                Voicing {
+                    frets: frets.clone(),
+                },
+            );
+            return;
+        };
+        frets.push(None);
+        self.search(frets, played_pitches, voicings);
+        frets.pop();
+        for (fret, pitch) in options {
+            frets.push(Some(*fret));
+            played_pitches.push(*pitch);
+            self.search(frets, played_pitches, voicings);
+            played_pitches.pop();
+            frets.pop();
+        }
+    }
+
+    /// Returns whether the strings chosen so far fit in one hand and the remaining
+    /// strings can still play the missing chord tones and the bass.
+    fn can_complete(&self, frets: &[Option<usize>], played_pitches: &[Pitch]) -> bool {
+        if get_stretch(frets) > self.max_stretch
+            || get_num_inner_muted_strings(frets) > 1
+            || get_num_fingers(frets) > NUM_FINGERS
+        {
+            return false;
+        }
+        let played_notes = played_pitches
+            .iter()
+            .map(|pitch| pitch.note)
+            .collect::<Vec<Note>>();
+        let missing_pitch_classes = self
+            .chord_pitch_classes
+            .intersection(PitchClassSet::from_notes(&played_notes).complement());
+        if !missing_pitch_classes.is_subset(&self.remaining_pitch_classes[frets.len()]) {
+            return false;
+        }
+        match played_pitches
+            .iter()
+            .min_by_key(|pitch| pitch.midi_number())
+        {
+            Some(lowest_pitch) => {
+                lowest_pitch.note == self.bass
+                    || self.remaining_lowest_midi_numbers[frets.len()] < lowest_pitch.midi_number()
+            }
+            None => true,
+        }
+    }
+}
+
+/// Inserts a voicing in a list of the best voicings sorted by rank, keeping at most
+/// `MAX_VOICINGS` of them. Voicings of the same rank keep the order they were found
+/// in.
+fn insert_voicing(voicings: &mut Vec<Voicing>, voicing: Voicing) {
+    let rank = voicing.rank();
+    let index = voicings.partition_point(|other| other.rank() <= rank);
+    if index < MAX_VOICINGS {
+        voicings.insert(index, voicing);
+        voicings.truncate(MAX_VOICINGS);
+    }
+}
+
+/// Draws a voicing as a chord box, with strings as columns from the thickest on the
+/// left and the frets of the window as rows.
+pub fn build_chord_box(voicing: &Voicing, starting_fret: usize, title: &str) -> Vec<String> {
+    let mut chord_box = vec![format!("{} {}", title, voicing.fingering())];
+    let open_strings = voicing
+        .frets
+        .iter()
+        .map(|fret| match fret {
+            None => "x",
+            Some(0) => "o",
+            Some(_) => " ",
+        })
+        .collect::<Vec<&str>>();
+    chord_box.push(open_strings.join(" "));
+    let nut_char = if starting_fret == 0 { "=" } else { "-" };
+    chord_box.push(nut_char.repeat(voicing.frets.len() * 2 - 1));
+    for fret in starting_fret.max(1)..starting_fret + FRET_SPAN {
+        let row = voicing
+            .frets
+            .iter()
+            .map(|played_fret| if *played_fret == Some(fret) { "O" } else { "|" })
+            .collect::<Vec<&str>>();
+        chord_box.push(format!("{} {}", row.join(" "), fret));
+    }
+    chord_box
+}
+
+/// Lays chord boxes out side by side.
+pub fn join_chord_boxes(chord_boxes: &[Vec<String>]) -> Vec<String> {
+    let num_rows = chord_boxes.iter().map(Vec::len).max().unwrap_or(0);
+    (0..num_rows)
+        .map(|row| {
+            chord_boxes
+                .iter()
+                .map(|chord_box| {
+                    let width = chord_box.iter().map(|line| line.chars().count()).max();
+                    let line = chord_box.get(row).map_or("", String::as_str);
+                    format!("{:<width$}", line, width = width.unwrap_or(0))
+                })
+                .collect::<Vec<String>>()
+                .join("    ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STANDARD_E6: [Pitch; 6] = [
+        Pitch::new(Note::E, 2),
+        Pitch::new(Note::A, 2),
+        Pitch::new(Note::D, 3),
+        Pitch::new(Note::G, 3),
+        Pitch::new(Note::B, 3),
+        Pitch::new(Note::E, 4),
+    ];
+
+    #[test]
+    fn test_find_voicings() {
+        let voicings = find_voicings(
+            &STANDARD_E6,
+            &[0; 6],
+            0,
+            &[Note::A, Note::C, Note::E, Note::G],
+            Note::A,
+            DEFAULT_MAX_STRETCH,
+        );
+        assert_eq!(voicings[0].fingering(), "x02010");
+        assert!(voicings
+            .iter()
+            .all(|voicing| voicing.frets[0].is_none() && voicing.frets[1] == Some(0)));
+
+        let voicings = find_voicings(
+            &STANDARD_E6,
+            &[0; 6],
+            7,
+            &[Note::C, Note::E, Note::G],
+            Note::C,
+            DEFAULT_MAX_STRETCH,
+        );
+        assert_eq!(voicings[0].fingering(), "8-10-10-9-8-8");
+        assert!(find_voicings(
+            &STANDARD_E6,
+            &[0; 6],
+            7,
+            &[Note::C, Note::E, Note::G],
+            Note::C,
+            1,
+        )
+        .iter()
+        .all(|voicing| voicing.stretch() <= 1));
+
+        let all_fourths = (0..14)
+            .map(|string| STANDARD_E6[0].transpose(string * 5))
+            .collect::<Vec<Pitch>>();
+        let voicings = find_voicings(
+            &all_fourths,
+            &[0; 14],
+            3,
+            &[Note::C, Note::E, Note::G, Note::ASharp],
+            Note::C,
+            4,
+        );
+        assert!(!voicings.is_empty() && voicings.len() <= MAX_VOICINGS);
+    }
+
+    #[test]
+    fn test_find_reentrant_voicings() {
+        let soprano_ukulele = [
+            Pitch::new(Note::G, 4),
+            Pitch::new(Note::C, 4),
+            Pitch::new(Note::E, 4),
+            Pitch::new(Note::A, 4),
+        ];
+        let voicings = find_voicings(
+            &soprano_ukulele,
+            &[0; 4],
+            0,
+            &[Note::C, Note::E, Note::G],
+            Note::C,
+            DEFAULT_MAX_STRETCH,
+        );
+        assert_eq!(voicings[0].fingering(), "0003");
+
+        let banjo_open_g = [
+            Pitch::new(Note::G, 4),
+            Pitch::new(Note::D, 3),
+            Pitch::new(Note::G, 3),
+            Pitch::new(Note::B, 3),
+            Pitch::new(Note::D, 4),
+        ];
+        let voicings = find_voicings(
+            &banjo_open_g,
+            &[5, 0, 0, 0, 0],
+            0,
+            &[Note::G, Note::B, Note::D],
+            Note::D,
+            DEFAULT_MAX_STRETCH,
+        );
+        assert!(voicings
+            .iter()
+            .any(|voicing| voicing.fingering() == "x0000"));
+        assert!(voicings.iter().all(|voicing| voicing.frets[1] == Some(0)));
+    }
+
+    #[test]
+    fn test_build_chord_box() {
+        let voicing = Voicing {
+            frets: vec![None, Some(0), Some(2), Some(0), Some(1), Some(0)],
+        };
+        assert_eq!(
+            build_chord_box(&voicing, 0, "Am7"),
+            vec![
+                "Am7 x02010",
+                "x o   o   o",
+                "===========",
+                "| | | | O | 1",
+                "| | O | | | 2",
+                "| | | | | | 3",
+                "| | | | | | 4",
+            ]
+        );
+        assert_eq!(
+            join_chord_boxes(&[
+                vec![String::from("ab"), String::from("c")],
+                vec![String::from("d")]
+            ]),
+            vec!["ab    d", "c"]
+        );
+    }
+}
@@ -1,17 +1,22 @@
 use daily_scale::lib::cli::{get_params, print_output, Params};
-use daily_scale::lib::fret_board::build_fret_board;
+use daily_scale::lib::fret_board::{build_edo_fret_board, build_fret_board};
 
 fn main() {
     let params = get_params();
     let Params {
-        tuning,
+        ref tuning,
+        ref capo,
         starting_fret,
-        ref notes_in_scale,
+        ref fret_board_notes,
+        ref edo_scale,
         ref format,
         ..
     } = params;
 
-    let fret_board = build_fret_board(tuning, starting_fret, notes_in_scale, format);
+    let fret_board = match edo_scale {
+        Some(edo_scale) => build_edo_fret_board(tuning, capo, starting_fret, edo_scale, format),
+        None => build_fret_board(tuning, capo, starting_fret, fret_board_notes, format),
+    };
 
     print_output(params, fret_board);
 }